@@ -0,0 +1,180 @@
+use std::cmp::Ordering;
+use std::ops::ControlFlow;
+use util::{parse_lines, Answer, Error, Result, Solution};
+
+/// The sum the puzzle looks for.
+pub const TARGET: i64 = 2020;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    /// The expense report entries, sorted in ascending order.
+    type Input<'a> = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Vec<i64>> {
+        load_and_sort_values(input)
+    }
+
+    fn part1(values: &Vec<i64>) -> Result<Answer> {
+        solve(values, 2, TARGET)
+    }
+
+    fn part2(values: &Vec<i64>) -> Result<Answer> {
+        solve(values, 3, TARGET)
+    }
+}
+
+fn load_and_sort_values(input: &str) -> Result<Vec<i64>> {
+    let mut values = parse_lines(input, str::parse::<i64>)?;
+    values.sort_unstable();
+    Ok(values)
+}
+
+/// Finds `k` entries that sum to `target` and multiplies them together.
+pub fn solve(values: &[i64], k: usize, target: i64) -> Result<Answer> {
+    let combination = k_sum(values, k, target)
+        .ok_or_else(|| Error::no_solution(format!("no {} entries sum to {}", k, target)))?;
+    product(&combination).map(Answer::Number)
+}
+
+/// The product of `values`, or an error if it doesn't fit in an `i128`.
+pub fn product(values: &[i64]) -> Result<i128> {
+    values
+        .iter()
+        .try_fold(1_i128, |product, &value| {
+            product.checked_mul(i128::from(value))
+        })
+        .ok_or_else(|| Error::invalid_input("the product of the entries overflows"))
+}
+
+/// Finds `k` of `values`, each used at most once, that sum to `target`, returning them in
+/// ascending order. Takes O(n^(k-1)) time for k of 2 or more: every choice of all but the last two
+/// values is tried, and the last two are found with two pointers.
+pub fn k_sum(values: &[i64], k: usize, target: i64) -> Option<Vec<i64>> {
+    let mut found = None;
+    search(values, k, target, |combination| {
+        found = Some(combination.to_vec());
+        ControlFlow::Break(())
+    });
+    found
+}
+
+/// Finds every distinct choice of `k` of `values`, each used at most once, that sum to `target`.
+/// Choices that differ only in which of several equal entries they use count as one. Each choice
+/// is in ascending order, and the choices are sorted.
+pub fn k_sums(values: &[i64], k: usize, target: i64) -> Vec<Vec<i64>> {
+    let mut found = Vec::new();
+    search(values, k, target, |combination| {
+        found.push(combination.to_vec());
+        ControlFlow::Continue(())
+    });
+    found
+}
+
+/// Calls `visit` with each distinct combination found, in ascending order, until it breaks.
+fn search<F>(values: &[i64], k: usize, target: i64, mut visit: F)
+where
+    F: FnMut(&[i64]) -> ControlFlow<()>,
+{
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let mut chosen = Vec::with_capacity(k);
+    let _ = find(&sorted, k, i128::from(target), &mut chosen, &mut visit);
+}
+
+/// Looks for `k` of the sorted `values` summing to `target`, calling `visit` with `chosen` plus
+/// each combination found. Sums are taken as `i128` so that no choice of `i64`s can overflow.
+fn find<F>(
+    values: &[i64],
+    k: usize,
+    target: i128,
+    chosen: &mut Vec<i64>,
+    visit: &mut F,
+) -> ControlFlow<()>
+where
+    F: FnMut(&[i64]) -> ControlFlow<()>,
+{
+    if values.len() < k {
+        return ControlFlow::Continue(());
+    }
+    match k {
+        0 if target == 0 => visit(chosen),
+        0 => ControlFlow::Continue(()),
+        1 => match values.binary_search_by(|&value| i128::from(value).cmp(&target)) {
+            Ok(i) => {
+                chosen.push(values[i]);
+                let flow = visit(chosen);
+                chosen.pop();
+                flow
+            }
+            Err(_) => ControlFlow::Continue(()),
+        },
+        2 => two_sum(values, target, chosen, visit),
+        _ => {
+            // The k - 1 largest values are the most any choice of them can add
+            let largest = sum(&values[values.len() - (k - 1)..]);
+            for (i, &value) in values[..=values.len() - k].iter().enumerate() {
+                // Trying a value that's the same as the last one can't find anything new
+                if i > 0 && values[i - 1] == value {
+                    continue;
+                }
+                // Every later value is at least as big, so if the smallest choice is already too
+                // big, so is every other
+                if sum(&values[i..i + k]) > target {
+                    break;
+                }
+                if i128::from(value) + largest < target {
+                    continue;
+                }
+                chosen.push(value);
+                let flow = find(
+                    &values[i + 1..],
+                    k - 1,
+                    target - i128::from(value),
+                    chosen,
+                    visit,
+                );
+                chosen.pop();
+                flow?;
+            }
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+fn two_sum<F>(values: &[i64], target: i128, chosen: &mut Vec<i64>, visit: &mut F) -> ControlFlow<()>
+where
+    F: FnMut(&[i64]) -> ControlFlow<()>,
+{
+    // Hold two pointers into the values, one starting at the bottom and one starting at the top.
+    // - If the sum of the values is less than the target, increment the bottom pointer.
+    // - If the sum of the values is greater than the target, decrement the top pointer.
+    // - If the sum is equal to the target, that's a pair. Move both pointers past any other copies
+    //   of its values, which would only find the same pair again.
+    // - If the pointers meet, there are no more pairs.
+    let (mut bottom, mut top) = (0_usize, values.len().saturating_sub(1));
+    while bottom < top {
+        let (low, high) = (values[bottom], values[top]);
+        match target.cmp(&(i128::from(low) + i128::from(high))) {
+            Ordering::Greater => bottom += 1,
+            Ordering::Equal => {
+                chosen.extend_from_slice(&[low, high]);
+                let flow = visit(chosen);
+                chosen.truncate(chosen.len() - 2);
+                flow?;
+                while bottom < top && values[bottom] == low {
+                    bottom += 1;
+                }
+                while bottom < top && values[top] == high {
+                    top -= 1;
+                }
+            }
+            Ordering::Less => top -= 1,
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+fn sum(values: &[i64]) -> i128 {
+    values.iter().map(|&value| i128::from(value)).sum()
+}
@@ -1,72 +1,109 @@
-use std::cmp::Ordering;
-use util::{read_lines, Result};
+use std::env;
+use util::input::Locator;
+use util::{Error, Result, Solution};
+use _01::Day01;
 
-fn main() -> Result<()> {
-    let solution1_result = solution_1()?;
-    println!("Solution 1 result: {}", solution1_result);
+const USAGE: &str = "usage: _01 [<input file> | -] [--k <k>] [--target <n>] [--all]";
 
-    let solution2_result = solution_2()?;
-    println!("Solution 2 result: {}", solution2_result);
-    Ok(())
-}
+fn main() -> Result<()> {
+    let mut locator = Locator::from_env();
+    let (mut k, mut target, mut all) = (None, None, false);
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--k" => {
+                let value = args
+                    .next()
+                    .and_then(|k| k.parse::<usize>().ok())
+                    .ok_or_else(|| Error::usage("`--k` expects a number of entries"))?;
+                k = Some(value);
+            }
+            "--target" => {
+                let value = args
+                    .next()
+                    .and_then(|target| target.parse::<i64>().ok())
+                    .ok_or_else(|| Error::usage("`--target` expects a number"))?;
+                target = Some(value);
+            }
+            "--all" => all = true,
+            _ if arg.starts_with("--") => {
+                return Err(Error::usage(format!("unknown flag `{}`\n{}", arg, USAGE)))
+            }
+            _ if locator.has_explicit_input() => {
+                return Err(Error::usage(format!(
+                    "unexpected argument `{}`\n{}",
+                    arg, USAGE
+                )))
+            }
+            _ => locator = locator.input(arg),
+        }
+    }
 
-fn load_and_sort_values() -> Result<Vec<i32>> {
-    let mut values = read_lines("_01/input.txt")?
-        .map(|line| line.parse::<i32>())
-        .collect::<std::result::Result<Vec<_>, _>>()?;
-    values.sort_unstable();
-    Ok(values)
-}
+    let input = locator.read(2020, 1)?;
+    let values = Day01::parse(&input)?;
 
-fn solution_1() -> Result<i32> {
-    // Initialize a vector of integers sorted in ascending order.
-    // - Index zero is considered the "bottom" of the vector
-    // - The last index is considered the "top" of the vector
-    let values = load_and_sort_values()?;
+    if all {
+        // Without `--k`, check both parts of the puzzle
+        let ks = k.map_or_else(|| vec![2, 3], |k| vec![k]);
+        return ks
+            .into_iter()
+            .try_for_each(|k| enumerate(&values, k, target.unwrap_or(_01::TARGET)));
+    }
 
-    // Hold two pointers into the values vector, one starting at the bottom and one starting at the
-    // top. Then begin a loop:
+    if k.is_none() && target.is_none() {
+        let solution1_result = Day01::part1(&values)?;
+        println!("Solution 1 result: {}", solution1_result);
 
-    // - If the sum of the values is less than 2020, increment the bottom pointer.
-    // - If the sum of the values is greater than 2020, decrement the top pointer.
-    // - If the sum is equal to 2020, return the product of the two values.
-    // - If the top pointer - the bottom pointer is less than two, return an error.
-    let (mut bottom, mut top) = (0_usize, values.len() - 1);
-    let total = loop {
-        if top - bottom < 2 {
-            // didn't find a solution
-            break Err("No solution found");
-        }
+        let solution2_result = Day01::part2(&values)?;
+        println!("Solution 2 result: {}", solution2_result);
+        return Ok(());
+    }
 
-        let (low, high) = (values[bottom], values[top]);
-        match 2020.cmp(&(low + high)) {
-            Ordering::Greater => bottom += 1,
-            Ordering::Equal => break Ok(low * high),
-            Ordering::Less => top -= 1,
-        }
-    }?;
-    Ok(total)
+    let (k, target) = (k.unwrap_or(2), target.unwrap_or(_01::TARGET));
+    let combination = _01::k_sum(&values, k, target)
+        .ok_or_else(|| Error::no_solution(format!("no {} entries sum to {}", k, target)))?;
+    println!("{} = {}", terms(&combination), target);
+    println!("product: {}", _01::product(&combination)?);
+    Ok(())
 }
 
-fn solution_2() -> Result<i32> {
-    let mut values = load_and_sort_values()?;
-
-    // As an optimization, pop elements so long as the last element plus the bottom two elements is
-    // greater than 2020
-    let bottom_sum = values[0] + values[1];
-    while bottom_sum + values.last().copied().unwrap() > 2020 {
-        values.pop();
+/// Prints every combination of `k` entries that sum to `target`, failing unless there's exactly
+/// one, so that hand-written inputs can be checked to have a single answer.
+fn enumerate(values: &[i64], k: usize, target: i64) -> Result<()> {
+    let combinations = _01::k_sums(values, k, target);
+    for combination in &combinations {
+        println!(
+            "{} = {} (product: {})",
+            terms(combination),
+            target,
+            _01::product(combination)?
+        );
     }
-
-    for p_bottom in 0..values.len() - 3 {
-        for p_mid in (p_bottom + 1)..values.len() - 2 {
-            for p_top in (p_mid..(values.len() - 1)).rev() {
-                let (bottom, mid, top) = (values[p_bottom], values[p_mid], values[p_top]);
-                if bottom + mid + top == 2020 {
-                    return Ok(bottom * mid * top);
-                }
-            }
-        }
+    println!(
+        "{} combination{} of {} entries sum{} to {}",
+        combinations.len(),
+        if combinations.len() == 1 { "" } else { "s" },
+        k,
+        if combinations.len() == 1 { "s" } else { "" },
+        target
+    );
+    match combinations.len() {
+        0 => Err(Error::no_solution(format!(
+            "no {} entries sum to {}",
+            k, target
+        ))),
+        1 => Ok(()),
+        count => Err(Error::invalid_input(format!(
+            "the input is ambiguous: {} different combinations of {} entries sum to {}",
+            count, k, target
+        ))),
     }
-    Err("No Solution".into())
+}
+
+fn terms(combination: &[i64]) -> String {
+    combination
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(" + ")
 }
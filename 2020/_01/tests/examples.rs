@@ -0,0 +1 @@
+util::example_tests!(_01::Day01);
@@ -0,0 +1,147 @@
+use std::collections::BTreeSet;
+use util::testing::Rng;
+use _01::{k_sum, k_sums, product};
+
+const EXAMPLE: [i64; 6] = [1721, 979, 366, 299, 675, 1456];
+
+/// Whether some `k` of `values` sum to `target`, trying every combination.
+fn brute_force(values: &[i64], k: usize, target: i64) -> bool {
+    match (k, values.split_first()) {
+        (0, _) => target == 0,
+        (_, None) => false,
+        (_, Some((&first, rest))) => {
+            brute_force(rest, k - 1, target - first) || brute_force(rest, k, target)
+        }
+    }
+}
+
+/// Every distinct combination of `k` of `values` that sum to `target`, each sorted.
+fn every_combination(values: &[i64], k: usize, target: i64) -> BTreeSet<Vec<i64>> {
+    let mut found = BTreeSet::new();
+    for mask in 0_u32..1 << values.len() {
+        let combination = (0..values.len())
+            .filter(|&i| mask & 1 << i != 0)
+            .map(|i| values[i])
+            .collect::<Vec<_>>();
+        if combination.len() == k && combination.iter().sum::<i64>() == target {
+            let mut sorted = combination;
+            sorted.sort_unstable();
+            found.insert(sorted);
+        }
+    }
+    found
+}
+
+/// Whether `combination` could have been taken from `values`, using each value at most once.
+fn is_drawn_from(combination: &[i64], values: &[i64]) -> bool {
+    let mut remaining = values.to_vec();
+    combination.iter().all(|value| {
+        match remaining.iter().position(|remaining| remaining == value) {
+            Some(i) => {
+                remaining.swap_remove(i);
+                true
+            }
+            None => false,
+        }
+    })
+}
+
+#[test]
+fn finds_the_combination_not_just_the_product() {
+    assert_eq!(k_sum(&EXAMPLE, 2, 2020), Some(vec![299, 1721]));
+    assert_eq!(k_sum(&EXAMPLE, 3, 2020), Some(vec![366, 675, 979]));
+    assert_eq!(product(&[366, 675, 979]).unwrap(), 241861950);
+}
+
+#[test]
+fn supports_any_k_and_target() {
+    assert_eq!(k_sum(&EXAMPLE, 1, 675), Some(vec![675]));
+    assert_eq!(k_sum(&EXAMPLE, 1, 676), None);
+    assert_eq!(k_sum(&EXAMPLE, 0, 0), Some(vec![]));
+    assert_eq!(
+        k_sum(&EXAMPLE, 6, EXAMPLE.iter().sum()),
+        Some({
+            let mut sorted = EXAMPLE.to_vec();
+            sorted.sort_unstable();
+            sorted
+        })
+    );
+    assert_eq!(k_sum(&EXAMPLE, 7, 0), None);
+    assert_eq!(
+        k_sum(&EXAMPLE, 4, 299 + 366 + 675 + 1456),
+        Some(vec![299, 366, 675, 1456])
+    );
+}
+
+#[test]
+fn uses_each_entry_at_most_once() {
+    assert_eq!(k_sum(&[1010], 2, 2020), None);
+    assert_eq!(k_sum(&[1010, 1010], 2, 2020), Some(vec![1010, 1010]));
+    assert_eq!(k_sum(&[5, 5, 5], 3, 15), Some(vec![5, 5, 5]));
+    assert_eq!(k_sum(&[5, 5], 3, 15), None);
+}
+
+#[test]
+fn enumerates_every_distinct_combination() {
+    assert_eq!(k_sums(&EXAMPLE, 2, 2020), vec![vec![299, 1721]]);
+    assert_eq!(k_sums(&EXAMPLE, 2, 2021), Vec::<Vec<i64>>::new());
+    assert_eq!(
+        k_sums(&[1, 1010, 1000, 2019, 1010, 1020], 2, 2020),
+        vec![vec![1, 2019], vec![1000, 1020], vec![1010, 1010]]
+    );
+    // Picking a different one of several equal entries isn't a different combination
+    assert_eq!(k_sums(&[5, 5, 5, 5], 2, 10), vec![vec![5, 5]]);
+    assert_eq!(
+        k_sums(&[1, 2, 2, 3, 3, 4], 3, 8),
+        vec![vec![1, 3, 4], vec![2, 2, 4], vec![2, 3, 3]]
+    );
+}
+
+#[test]
+fn handles_negative_and_extreme_values() {
+    assert_eq!(k_sum(&[-5, 3, 10, -2], 3, 3), Some(vec![-5, -2, 10]));
+    assert_eq!(k_sum(&[i64::MAX, i64::MAX, 1], 2, -2), None);
+    assert_eq!(
+        k_sum(&[i64::MIN, i64::MAX], 2, -1),
+        Some(vec![i64::MIN, i64::MAX])
+    );
+    assert!(product(&[i64::MAX, i64::MAX, i64::MAX]).is_err());
+}
+
+#[test]
+fn agrees_with_trying_every_combination() {
+    let mut rng = Rng::new(1);
+    for _ in 0..2000 {
+        let values = (0..rng.below(12))
+            .map(|_| rng.between(-20, 20))
+            .collect::<Vec<_>>();
+        let k = rng.below(5);
+        let target = rng.between(-30, 30);
+        let found = k_sum(&values, k, target);
+        assert_eq!(
+            found.is_some(),
+            brute_force(&values, k, target),
+            "{:?} {} {}",
+            values,
+            k,
+            target
+        );
+        let all = k_sums(&values, k, target);
+        assert_eq!(
+            all.iter().cloned().collect::<BTreeSet<_>>(),
+            every_combination(&values, k, target),
+            "{:?} {} {}",
+            values,
+            k,
+            target
+        );
+        assert!(all.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(found.as_ref(), all.first());
+        if let Some(combination) = found {
+            assert_eq!(combination.len(), k);
+            assert_eq!(combination.iter().sum::<i64>(), target);
+            assert!(combination.windows(2).all(|pair| pair[0] <= pair[1]));
+            assert!(is_drawn_from(&combination, &values));
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use nom::bytes::complete::{tag, take, take_while};
+use nom::character::complete::newline;
+use nom::character::{is_alphanumeric, is_digit};
+use nom::combinator::{eof, map, map_res};
+use nom::multi::many_till;
+use nom::sequence::{separated_pair, terminated, tuple};
+use nom::IResult;
+use std::ops::BitXor;
+use util::{Answer, Error, Result, Solution};
+
+#[derive(Debug)]
+pub struct Entry<'a> {
+    required_letter: char,
+    required_occurrences: (u32, u32),
+    password: &'a [u8],
+}
+
+impl<'a> Entry<'a> {
+    fn is_valid_solution_1(&self) -> bool {
+        let (min_occur, max_occur) = self.required_occurrences;
+        let mut occurs = 0;
+
+        for chr in self.password.iter().copied().map(|chr| chr as char) {
+            if chr == self.required_letter {
+                occurs += 1;
+                if occurs > max_occur {
+                    return false;
+                }
+            }
+        }
+
+        occurs >= min_occur
+    }
+
+    fn is_valid_solution_2(&self) -> Result<bool> {
+        let (pos_1, pos_2) = self.required_occurrences;
+        let char1_match = (pos_1 as usize)
+            .checked_sub(1)
+            .and_then(|index| self.password.get(index))
+            .copied()
+            .map(|chr| (chr as char) == self.required_letter)
+            .ok_or_else(|| Error::invalid_input("Position out of bounds"))?;
+
+        let char2_match = (pos_2 as usize)
+            .checked_sub(1)
+            .and_then(|index| self.password.get(index))
+            .copied()
+            .map(|chr| (chr as char) == self.required_letter)
+            .ok_or_else(|| Error::invalid_input("Position out of bounds"))?;
+        Ok(char1_match.bitxor(char2_match))
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input<'a> = Vec<Entry<'a>>;
+
+    fn parse(input: &str) -> Result<Vec<Entry<'_>>> {
+        parse_entries(input)
+    }
+
+    fn part1(entries: &Vec<Entry<'_>>) -> Result<Answer> {
+        Ok(entries
+            .iter()
+            .map(|entry| if entry.is_valid_solution_1() { 1 } else { 0 })
+            .sum::<u32>()
+            .into())
+    }
+
+    fn part2(entries: &Vec<Entry<'_>>) -> Result<Answer> {
+        let mut valid_entries = 0_u32;
+        for entry in entries.iter() {
+            if entry.is_valid_solution_2()? {
+                valid_entries += 1;
+            }
+        }
+        Ok(valid_entries.into())
+    }
+}
+
+fn parse_entries(input: &str) -> Result<Vec<Entry<'_>>> {
+    fn parse_number(input: &[u8]) -> IResult<&[u8], u32> {
+        map_res(take_while(is_digit), |bytes: &[u8]| {
+            std::str::from_utf8(bytes)
+                .map_err(|_| "Failed to convert bytes to string")
+                .and_then(|digits| digits.parse::<u32>().map_err(|_| "Failed to parse number"))
+        })(input)
+    }
+
+    fn parse_range(input: &[u8]) -> IResult<&[u8], (u32, u32)> {
+        terminated(
+            separated_pair(parse_number, tag("-"), parse_number),
+            tag(" "),
+        )(input)
+    }
+
+    fn parse_required_letter(input: &[u8]) -> IResult<&[u8], char> {
+        map_res(terminated(take(1_usize), tag(": ")), |chr: &[u8]| {
+            chr.first().copied().map(|chr| chr as char).ok_or(())
+        })(input)
+    }
+
+    let (_, (entries, _)) = many_till(
+        map(
+            terminated(
+                tuple((
+                    parse_range,
+                    parse_required_letter,
+                    take_while(is_alphanumeric),
+                )),
+                newline,
+            ),
+            |(required_occurrences, required_letter, password)| Entry {
+                required_occurrences,
+                required_letter,
+                password,
+            },
+        ),
+        eof,
+    )(input.as_bytes())
+    .map_err(|err| Error::from_nom(input, err))?;
+
+    Ok(entries)
+}
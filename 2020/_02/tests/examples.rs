@@ -0,0 +1 @@
+util::example_tests!(_02::Day02);
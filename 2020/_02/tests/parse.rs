@@ -0,0 +1,11 @@
+use util::{Error, Solution};
+use _02::Day02;
+
+#[test]
+fn reports_a_non_ascii_letter_instead_of_panicking() {
+    match Day02::parse("1-3 é: abc\n") {
+        Err(Error::Parse { line, column, .. }) => assert_eq!((line, column), (1, 5)),
+        Err(err) => panic!("expected a parse error, got {}", err),
+        Ok(_) => panic!("expected a parse error"),
+    }
+}
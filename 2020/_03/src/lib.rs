@@ -0,0 +1,50 @@
+use util::grid::Grid;
+use util::{Answer, Result, Solution};
+
+pub struct Board {
+    /// Whether each square holds a tree.
+    trees: Grid<bool>,
+}
+
+impl Board {
+    fn check_path(&self, x_step: usize, y_step: usize) -> usize {
+        // The map repeats to the right, but not downwards
+        (y_step..self.trees.height())
+            .step_by(y_step)
+            .map(|height| ((height / y_step) * x_step, height))
+            .filter(|&(x, y)| *self.trees.get_wrapping(x as isize, y as isize))
+            .count()
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input<'a> = Board;
+
+    fn parse(input: &str) -> Result<Board> {
+        let trees = Grid::parse(input, |square| match square {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err("expected '.' or '#'"),
+        })?;
+        Ok(Board { trees })
+    }
+
+    fn part1(board: &Board) -> Result<Answer> {
+        Ok(board.check_path(3, 1).into())
+    }
+
+    fn part2(board: &Board) -> Result<Answer> {
+        Ok([
+            board.check_path(1, 1),
+            board.check_path(3, 1),
+            board.check_path(5, 1),
+            board.check_path(7, 1),
+            board.check_path(1, 2),
+        ]
+        .iter()
+        .product::<usize>()
+        .into())
+    }
+}
@@ -0,0 +1 @@
+util::example_tests!(_03::Day03);
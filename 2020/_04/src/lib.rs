@@ -0,0 +1,147 @@
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take, take_while};
+use nom::character::complete::{line_ending, space1};
+use nom::combinator::{eof, map};
+use nom::multi::many_till;
+use nom::sequence::{pair, preceded, separated_pair, terminated};
+use std::collections::HashMap;
+use util::{Answer, Error, Result, Solution};
+
+const REQUIRED_FIELDS: &[&str] = &["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+
+fn has_required_fields(doc: &HashMap<&str, &str>) -> bool {
+    REQUIRED_FIELDS
+        .iter()
+        .all(|required_field| doc.contains_key(required_field))
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    /// Each passport's fields, keyed by field name.
+    type Input<'a> = Vec<HashMap<&'a str, &'a str>>;
+
+    fn parse(input: &str) -> Result<Vec<HashMap<&str, &str>>> {
+        parse_docs(input)
+    }
+
+    fn part1(docs: &Vec<HashMap<&str, &str>>) -> Result<Answer> {
+        Ok(docs
+            .iter()
+            .filter(|doc| has_required_fields(doc))
+            .count()
+            .into())
+    }
+
+    fn part2(docs: &Vec<HashMap<&str, &str>>) -> Result<Answer> {
+        Ok(docs
+            .iter()
+            .filter(|doc| {
+                has_required_fields(doc)
+                    && doc.iter().all(|(key, value)| validate_field(key, value))
+            })
+            .count()
+            .into())
+    }
+}
+
+fn validate_field(key: &str, value: &str) -> bool {
+    match key {
+        "byr" => {
+            value.len() == 4
+                && value
+                    .parse::<u16>()
+                    .map(|value| (1920..=2002).contains(&value))
+                    .unwrap_or(false)
+        }
+        "iyr" => {
+            value.len() == 4
+                && value
+                    .parse::<u16>()
+                    .map(|value| (2010..=2020).contains(&value))
+                    .unwrap_or(false)
+        }
+        "eyr" => {
+            value.len() == 4
+                && value
+                    .parse::<u16>()
+                    .map(|value| (2020..=2030).contains(&value))
+                    .unwrap_or(false)
+        }
+        "hgt" => map::<_, _, _, nom::error::Error<&str>, _, _>(
+            pair(
+                take_while(|chr: char| chr.is_ascii_digit()),
+                alt((tag("cm"), tag("in"))),
+            ),
+            |(height, uom): (&str, &str)| {
+                let height = match height.parse::<u16>() {
+                    Ok(height) => height,
+                    Err(_) => return false,
+                };
+                match uom {
+                    "cm" => (150..=193).contains(&height),
+                    "in" => (59..=76).contains(&height),
+                    _ => false,
+                }
+            },
+        )(value)
+        .map(|(_, result)| result)
+        .unwrap_or(false),
+        "hcl" => map::<_, _, _, nom::error::Error<&str>, _, _>(
+            preceded(tag("#"), take(6_usize)),
+            |value: &str| {
+                value.chars().all(|chr| {
+                    chr.is_ascii_hexdigit() && (chr.is_ascii_digit() || chr.is_lowercase())
+                })
+            },
+        )(value)
+        .map(|(_, result)| result)
+        .unwrap_or(false),
+        "ecl" => map::<_, _, _, nom::error::Error<&str>, _, _>(
+            alt((
+                tag("amb"),
+                tag("blu"),
+                tag("brn"),
+                tag("gry"),
+                tag("grn"),
+                tag("hzl"),
+                tag("oth"),
+            )),
+            |_| true,
+        )(value)
+        .map(|(_, result)| result)
+        .unwrap_or(false),
+        "pid" => map::<_, _, _, nom::error::Error<&str>, _, _>(
+            take_while(|chr: char| chr.is_ascii_digit()),
+            |pid: &str| pid.len() == 9,
+        )(value)
+        .map(|(_, result)| result)
+        .unwrap_or(false),
+        "cid" => true,
+        _ => false,
+    }
+}
+
+fn parse_docs(input: &str) -> Result<Vec<HashMap<&str, &str>>> {
+    let (_, (passports, _)) = many_till::<_, _, _, nom::error::Error<&str>, _, _>(
+        map(
+            many_till(
+                terminated(
+                    separated_pair(
+                        take(3_usize),
+                        tag(":"),
+                        take_while(|chr: char| {
+                            chr.is_ascii_alphanumeric() || chr.is_ascii_punctuation()
+                        }),
+                    ),
+                    alt((space1, line_ending)),
+                ),
+                alt((line_ending, eof)),
+            ),
+            |(items, _)| items.into_iter().collect::<HashMap<_, _>>(),
+        ),
+        eof,
+    )(input)
+    .map_err(|err| Error::from_nom(input, err))?;
+    Ok(passports)
+}
@@ -0,0 +1 @@
+util::example_tests!(_04::Day04);
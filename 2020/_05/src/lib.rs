@@ -0,0 +1,54 @@
+use util::{parse_lines, Answer, Error, LineError, Result, Solution};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    /// The seat ids of every boarding pass, sorted in ascending order.
+    type Input<'a> = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Vec<usize>> {
+        let mut ids = parse_lines(input, |line| {
+            // Point at the first character that's out of place, or where the pass ends too soon
+            let bad = line.bytes().enumerate().position(|(i, chr)| match i {
+                0..=6 => chr != b'F' && chr != b'B',
+                7..=9 => chr != b'L' && chr != b'R',
+                _ => true,
+            });
+            match bad {
+                None if line.len() == 10 => Ok(bsp(&line[..7]) * 8 + bsp(&line[7..])),
+                bad => Err(LineError::at(
+                    bad.unwrap_or(line.len()),
+                    "expected 7 F/B characters followed by 3 L/R characters",
+                )),
+            }
+        })?;
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    fn part1(ids: &Vec<usize>) -> Result<Answer> {
+        ids.last()
+            .copied()
+            .map(Answer::from)
+            .ok_or_else(|| Error::no_solution("there are no boarding passes"))
+    }
+
+    fn part2(ids: &Vec<usize>) -> Result<Answer> {
+        ids.iter()
+            .copied()
+            .enumerate()
+            .find(|(i, id)| *id != i + ids[0])
+            .map(|(_, id)| Answer::from(id - 1))
+            .ok_or_else(|| Error::no_solution("there is no gap between the seat ids"))
+    }
+}
+
+fn bsp(input: &str) -> usize {
+    input.chars().enumerate().fold(0, |acc, (i, chr)| {
+        if chr == 'B' || chr == 'R' {
+            acc + 2_usize.pow((input.len() - i - 1) as u32)
+        } else {
+            acc
+        }
+    })
+}
@@ -0,0 +1,12 @@
+use util::{Error, Solution};
+use _05::Day05;
+
+#[test]
+fn reports_a_missing_gap_instead_of_panicking() {
+    // Seats 357, 358 and 359, with nothing missing between them
+    let ids = Day05::parse("FBFBBFFRLR\nFBFBBFFRRL\nFBFBBFFRRR\n").unwrap();
+    assert!(matches!(Day05::part2(&ids), Err(Error::NoSolution(_))));
+    let none = Day05::parse("").unwrap();
+    assert!(matches!(Day05::part1(&none), Err(Error::NoSolution(_))));
+    assert!(matches!(Day05::part2(&none), Err(Error::NoSolution(_))));
+}
@@ -0,0 +1 @@
+util::example_tests!(_05::Day05);
@@ -0,0 +1,49 @@
+use std::collections::HashSet;
+use std::iter::FromIterator;
+use util::{Answer, Result, Solution};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    /// Each group's answers, one set of questions per group member.
+    type Input<'a> = Vec<Vec<HashSet<char>>>;
+
+    fn parse(input: &str) -> Result<Vec<Vec<HashSet<char>>>> {
+        let mut groups = vec![Vec::<HashSet<char>>::default()];
+        for line in input.lines() {
+            if line.is_empty() {
+                groups.push(Vec::default());
+            } else {
+                groups
+                    .last_mut()
+                    .unwrap()
+                    .push(HashSet::from_iter(line.chars()))
+            }
+        }
+        Ok(groups)
+    }
+
+    fn part1(groups: &Vec<Vec<HashSet<char>>>) -> Result<Answer> {
+        Ok(groups
+            .iter()
+            .map(|group| group.iter().flatten().collect::<HashSet<_>>().len())
+            .sum::<usize>()
+            .into())
+    }
+
+    fn part2(groups: &Vec<Vec<HashSet<char>>>) -> Result<Answer> {
+        Ok(groups
+            .iter()
+            .map(|group| {
+                let mut set = group.first().cloned().unwrap_or_default();
+                for member in group {
+                    set.clone().symmetric_difference(member).for_each(|diff| {
+                        set.remove(diff);
+                    });
+                }
+                set.len()
+            })
+            .sum::<usize>()
+            .into())
+    }
+}
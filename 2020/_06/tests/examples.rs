@@ -0,0 +1 @@
+util::example_tests!(_06::Day06);
@@ -0,0 +1,139 @@
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_until, take_while};
+use nom::character::complete::newline;
+use nom::combinator::{eof, map, map_res};
+use nom::multi::many_till;
+use nom::sequence::{pair, separated_pair, terminated};
+use nom::IResult;
+use std::collections::{HashMap, HashSet};
+use util::{Answer, Error, Result, Solution};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    /// The bags each color of bag must contain, keyed by color.
+    type Input<'a> = HashMap<&'a str, HashMap<&'a str, u32>>;
+
+    fn parse(input: &str) -> Result<HashMap<&str, HashMap<&str, u32>>> {
+        parse(input)
+    }
+
+    fn part1(bags: &HashMap<&str, HashMap<&str, u32>>) -> Result<Answer> {
+        Ok(bags
+            .keys()
+            .filter(|bag| contains_nested(bag, bags, "shiny gold", &mut HashSet::default()))
+            .count()
+            .into())
+    }
+
+    fn part2(bags: &HashMap<&str, HashMap<&str, u32>>) -> Result<Answer> {
+        Ok(find_total_bags_containing("shiny gold", bags, &mut Vec::new())?.into())
+    }
+}
+
+/// `outer` holds the colors of the bags currently being counted, which `color` is nested within.
+fn find_total_bags_containing<'a>(
+    color: &'a str,
+    bags: &HashMap<&'a str, HashMap<&'a str, u32>>,
+    outer: &mut Vec<&'a str>,
+) -> Result<u32> {
+    if outer.contains(&color) {
+        return Err(Error::invalid_input(format!(
+            "{} bags must contain themselves",
+            color
+        )));
+    }
+
+    if let Some(can_contain) = bags.get(color) {
+        outer.push(color);
+        let mut num_bags = 0_u32;
+        for (inner_bag, inner_amount) in can_contain.iter() {
+            let inner_total = find_total_bags_containing(inner_bag, bags, outer)?;
+            num_bags = inner_total
+                .checked_add(1)
+                .and_then(|per_bag| per_bag.checked_mul(*inner_amount))
+                .and_then(|total| total.checked_add(num_bags))
+                .ok_or_else(|| Error::invalid_input("Too many bags to count"))?;
+        }
+        outer.pop();
+        Ok(num_bags)
+    } else {
+        Ok(0)
+    }
+}
+
+/// `visited` holds the colors already searched, so that bags which (invalidly) contain themselves
+/// aren't searched forever.
+fn contains_nested<'a>(
+    color: &'a str,
+    bags: &HashMap<&'a str, HashMap<&'a str, u32>>,
+    looking_for: &str,
+    visited: &mut HashSet<&'a str>,
+) -> bool {
+    if !visited.insert(color) {
+        return false;
+    }
+
+    if let Some(can_contain) = bags.get(color) {
+        if can_contain.is_empty() {
+            false
+        } else if can_contain.contains_key(looking_for) {
+            true
+        } else {
+            for bag in can_contain.keys() {
+                if contains_nested(bag, bags, looking_for, visited) {
+                    return true;
+                }
+            }
+            false
+        }
+    } else {
+        false
+    }
+}
+
+fn parse(input: &str) -> Result<HashMap<&str, HashMap<&str, u32>>> {
+    fn parse_color(input: &str) -> IResult<&str, &str> {
+        terminated(take_until(" bag"), alt((tag(" bags"), tag(" bag"))))(input)
+    }
+
+    fn parse_bag_and_amount(input: &str) -> IResult<&str, (u32, &str)> {
+        separated_pair(
+            map_res(take_while(|chr: char| chr.is_ascii_digit()), |num: &str| {
+                num.parse::<u32>()
+            }),
+            tag(" "),
+            parse_color,
+        )(input)
+    }
+
+    fn parse_contained_bags(input: &str) -> IResult<&str, HashMap<&str, u32>> {
+        alt((
+            map(terminated(tag("no other bags."), newline), |_| {
+                HashMap::default()
+            }),
+            map(
+                many_till(
+                    terminated(parse_bag_and_amount, alt((tag(", "), tag(".")))),
+                    newline,
+                ),
+                |(a, _)| {
+                    a.into_iter()
+                        .map(|(a, b)| (b, a))
+                        .collect::<HashMap<_, _>>()
+                },
+            ),
+        ))(input)
+    }
+
+    fn parse_line(input: &str) -> IResult<&str, (&str, HashMap<&str, u32>)> {
+        pair(
+            terminated(parse_color, tag(" contain ")),
+            parse_contained_bags,
+        )(input)
+    }
+
+    let (_, (bags, _)) =
+        many_till(parse_line, eof)(input).map_err(|err| Error::from_nom(input, err))?;
+    Ok(bags.into_iter().collect::<HashMap<_, _>>())
+}
@@ -0,0 +1 @@
+util::example_tests!(_07::Day07);
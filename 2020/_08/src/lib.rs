@@ -0,0 +1,40 @@
+use util::{Answer, Error, Result, Solution};
+use vm::{repair, Cpu, Instr, Outcome};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input<'a> = Vec<Instr>;
+
+    fn parse(input: &str) -> Result<Vec<Instr>> {
+        vm::assemble(input)
+    }
+
+    fn part1(code: &Vec<Instr>) -> Result<Answer> {
+        Ok(find_infinite_loop(Cpu::new(code.clone()))?.into())
+    }
+
+    fn part2(code: &Vec<Instr>) -> Result<Answer> {
+        Ok(find_corrupt_instr(code)?.into())
+    }
+}
+
+fn find_corrupt_instr(program: &[Instr]) -> Result<i64> {
+    repair::repair(program)
+        .map(|repair| repair.acc)
+        .ok_or_else(|| Error::no_solution("no single jmp/nop swap makes the program terminate"))
+}
+
+fn find_infinite_loop(mut cpu: Cpu) -> Result<i64> {
+    match cpu.run() {
+        Outcome::InfiniteLoop { acc, .. } => Ok(acc),
+        Outcome::Terminated { .. } => {
+            Err(Error::no_solution("the program terminates without looping"))
+        }
+        Outcome::OutOfBounds { pc } => Err(Error::invalid_input(format!(
+            "Program counter {} is outside of the program",
+            pc
+        ))),
+        Outcome::StepLimit => unreachable!("the program ran without a step limit"),
+    }
+}
@@ -1,128 +1,206 @@
-use nom::branch::alt;
-use nom::bytes::complete::{tag, take_until};
-use nom::character::complete::newline;
-use nom::combinator::{eof, map};
-use nom::multi::many_till;
-use nom::sequence::{pair, terminated};
-use nom::IResult;
-use std::collections::HashSet;
-use std::convert::TryFrom;
-use std::io::Read;
-use util::Result;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use util::input::Locator;
+use util::{Error, Result, Solution};
+use vm::cfg::{Cfg, Fate};
+use vm::debugger::{Command, Debugger};
+use vm::{repair, trace, Cpu, Instr};
+use _08::Day08;
 
-#[derive(Copy, Clone, Debug)]
-enum Instr {
-    Acc(i32),
-    Nop(i32),
-    Jmp(i32),
-}
-
-struct Cpu {
-    code: Vec<Instr>,
-    acc: i32,
-    pc: i32,
-}
-
-impl Cpu {
-    fn new(code: Vec<Instr>) -> Cpu {
-        Cpu {
-            code,
-            acc: 0,
-            pc: 0,
-        }
-    }
+const USAGE: &str = "usage: _08 [<input file> | -] --debug\n       \
+                     _08 [<input file> | -] [--all-repairs] [--trace <file>] \
+                     [--trace-repaired <file>] [--analyse] [--dot <file>] [--save-repaired <file>]";
 
-    /// Returns the program counter after stepping
-    fn step(&mut self) -> i32 {
-        match self.code[self.pc as usize] {
-            Instr::Acc(val) => {
-                self.acc += val;
-                self.pc += 1;
+fn main() -> Result<()> {
+    let mut locator = Locator::from_env();
+    let (mut all_repairs, mut debug, mut analyse) = (false, false, false);
+    let mut dot = None;
+    let (mut trace, mut trace_repaired, mut save_repaired) = (None, None, None);
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all-repairs" => all_repairs = true,
+            "--debug" => debug = true,
+            "--analyse" => analyse = true,
+            "--dot" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| Error::usage("`--dot` expects a file"))?;
+                dot = Some(PathBuf::from(path));
+            }
+            "--save-repaired" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| Error::usage("`--save-repaired` expects a file"))?;
+                save_repaired = Some(PathBuf::from(path));
+            }
+            "--trace" | "--trace-repaired" => {
+                let path = args
+                    .next()
+                    .map(PathBuf::from)
+                    .ok_or_else(|| Error::usage(format!("`{}` expects a file", arg)))?;
+                // Check the format up front rather than after running the program
+                trace::Format::from_path(&path)?;
+                if arg == "--trace" {
+                    trace = Some(path);
+                } else {
+                    trace_repaired = Some(path);
+                }
+            }
+            _ if arg.starts_with("--") => {
+                return Err(Error::usage(format!("unknown flag `{}`\n{}", arg, USAGE)))
             }
-            Instr::Nop(_) => {
-                self.pc += 1;
+            _ if locator.has_explicit_input() => {
+                return Err(Error::usage(format!(
+                    "unexpected argument `{}`\n{}",
+                    arg, USAGE
+                )))
             }
-            Instr::Jmp(val) => self.pc += val,
+            _ => locator = locator.input(arg),
         }
-        self.pc
     }
-}
 
-fn main() -> Result<()> {
-    let mut buf = String::new();
-    let mut file = std::fs::File::open("_08/input.txt")?;
-    file.read_to_string(&mut buf)?;
-    let code = parse(&buf)?;
-    let cpu = Cpu::new(code.clone());
-    let infinite_loop = find_infinite_loop(cpu);
-    let corrupt_fix = find_corrupt_instr(code);
-    println!("{}", infinite_loop);
-    println!("{}", corrupt_fix);
-    Ok(())
-}
+    if debug {
+        // The debugger takes over the run, so nothing else asked for would happen
+        let conflicts = [
+            ("--all-repairs", all_repairs),
+            ("--analyse", analyse),
+            ("--dot", dot.is_some()),
+            ("--trace", trace.is_some()),
+            ("--trace-repaired", trace_repaired.is_some()),
+            ("--save-repaired", save_repaired.is_some()),
+        ];
+        if let Some((flag, _)) = conflicts.iter().find(|(_, given)| *given) {
+            return Err(Error::usage(format!(
+                "`--debug` can't be combined with `{}`\n{}",
+                flag, USAGE
+            )));
+        }
+    }
 
-fn find_corrupt_instr(orig_instructions: Vec<Instr>) -> i32 {
-    let success_pc = i32::try_from(orig_instructions.len()).unwrap();
-    let mut instr_to_swap = 0;
-    loop {
-        let mut copy = orig_instructions.clone();
-        match copy[instr_to_swap] {
-            Instr::Jmp(val) => copy[instr_to_swap] = Instr::Nop(val),
-            Instr::Nop(val) => copy[instr_to_swap] = Instr::Jmp(val),
-            _ => {
-                instr_to_swap += 1;
-                continue;
-            }
+    let input = locator.read(2020, 8)?;
+    let code = Day08::parse(&input)?;
+    if debug {
+        return run_debugger(code);
+    }
+    if analyse {
+        print_analysis(&Cfg::new(&code));
+    }
+    if let Some(path) = dot {
+        std::fs::write(path, Cfg::new(&code).to_dot())?;
+    }
+    if let Some(path) = trace {
+        write_trace(&code, &path)?;
+    }
+    if trace_repaired.is_some() || save_repaired.is_some() {
+        let repaired = repair::repair(&code)
+            .ok_or_else(|| {
+                Error::no_solution("no single jmp/nop swap makes the program terminate")
+            })?
+            .apply(&code);
+        if let Some(path) = trace_repaired {
+            write_trace(&repaired, &path)?;
         }
-        let mut cpu = Cpu::new(copy);
-        for _ in 0..1000 {
-            if cpu.step() == success_pc {
-                break;
-            }
+        if let Some(path) = save_repaired {
+            std::fs::write(path, vm::disassemble(&repaired))?;
         }
-
-        if cpu.pc == success_pc {
-            break cpu.acc;
-        } else if instr_to_swap == orig_instructions.len() - 1 {
-            panic!("No solution fond");
+    }
+    println!("{}", Day08::part1(&code)?);
+    if all_repairs {
+        let repairs = repair::repairs(&code);
+        for repair in &repairs {
+            println!(
+                "line {}: {} -> {} terminates with acc {}",
+                repair.pc + 1,
+                code[repair.pc],
+                repair.replacement,
+                repair.acc
+            );
         }
-        instr_to_swap += 1;
+        println!("{} repairs", repairs.len());
+    } else {
+        println!("{}", Day08::part2(&code)?);
     }
+    Ok(())
 }
 
-fn find_infinite_loop(mut cpu: Cpu) -> i32 {
-    let mut executed_instructions = HashSet::<i32>::default();
-    loop {
-        let acc = cpu.acc;
-        let pc = cpu.step();
-        if executed_instructions.contains(&pc) {
-            break acc;
-        } else {
-            executed_instructions.insert(pc);
-        }
+/// Explains what the program does without running it.
+fn print_analysis(cfg: &Cfg) {
+    println!(
+        "{} basic blocks, {} instructions never terminate",
+        cfg.blocks().len(),
+        cfg.never_terminates().len()
+    );
+    let unreachable = cfg.unreachable();
+    println!("{} instructions are unreachable", unreachable.len());
+    for (pc, target) in cfg.out_of_range() {
+        println!(
+            "the jump at pc {} goes to pc {}, outside of the program",
+            pc, target
+        );
+    }
+    if let Some(path) = cfg.entry_path() {
+        let ending = match path.fate {
+            Fate::Terminates => "terminates".to_string(),
+            Fate::Loops(block) => format!("loops back to pc {}", cfg.blocks()[block].start),
+            Fate::LeavesProgram(target) => format!("leaves the program for pc {}", target),
+        };
+        println!(
+            "a run passes through {} blocks and {} with acc {}",
+            path.blocks.len(),
+            ending,
+            path.acc
+        );
     }
 }
 
-fn parse(input: &str) -> std::result::Result<Vec<Instr>, &'static str> {
-    fn parse_instr(input: &str) -> IResult<&str, Instr> {
-        terminated(
-            map(
-                pair(
-                    terminated(alt((tag("nop"), tag("acc"), tag("jmp"))), tag(" ")),
-                    map(take_until("\n"), |val: &str| val.parse::<i32>().unwrap()),
-                ),
-                |(instr, val)| match instr {
-                    "nop" => Instr::Nop(val),
-                    "acc" => Instr::Acc(val),
-                    "jmp" => Instr::Jmp(val),
-                    _ => unimplemented!(),
-                },
-            ),
-            newline,
-        )(input)
-    }
+/// Runs a program until it terminates, loops or leaves the program, writing a trace of the run to
+/// `path` in the format its extension names.
+fn write_trace(program: &[Instr], path: &Path) -> Result<()> {
+    let mut cpu = Cpu::new(program.to_vec()).with_trace();
+    cpu.run();
+    let file = BufWriter::new(File::create(path)?);
+    trace::export(
+        cpu.trace().unwrap_or_default(),
+        trace::Format::from_path(path)?,
+        file,
+    )?;
+    Ok(())
+}
 
-    let (_, (instructions, _)) =
-        many_till(parse_instr, eof)(input).map_err(|_| "Failed to parse")?;
-    Ok(instructions)
+/// Reads debugger commands from stdin until it's closed or told to quit. An empty line repeats the
+/// previous command.
+fn run_debugger(program: Vec<Instr>) -> Result<()> {
+    let mut debugger = Debugger::new(program);
+    let mut previous = None;
+    let stdin = io::stdin();
+    loop {
+        print!("(vm) ");
+        io::stdout().flush()?;
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            break;
+        }
+        let command = match line.trim() {
+            "" => match previous {
+                Some(command) => command,
+                None => continue,
+            },
+            line => match line.parse::<Command>() {
+                Ok(command) => command,
+                Err(err) => {
+                    println!("{}", err);
+                    continue;
+                }
+            },
+        };
+        if command == Command::Quit {
+            break;
+        }
+        println!("{}", debugger.execute(command));
+        previous = Some(command);
+    }
+    Ok(())
 }
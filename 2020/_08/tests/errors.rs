@@ -0,0 +1,20 @@
+use util::{Error, Solution};
+use _08::Day08;
+
+#[test]
+fn reports_a_program_with_no_repair_instead_of_panicking() {
+    // Flipping the only instruction that runs just moves the loop to the next one
+    let program = Day08::parse("jmp +0\njmp +0\n").unwrap();
+    assert!(matches!(Day08::part2(&program), Err(Error::NoSolution(_))));
+}
+
+#[test]
+fn reports_a_program_that_never_loops() {
+    let terminates = Day08::parse("acc +1\nnop +0\n").unwrap();
+    assert!(matches!(
+        Day08::part1(&terminates),
+        Err(Error::NoSolution(_))
+    ));
+    let leaves = Day08::parse("jmp +5\n").unwrap();
+    assert!(matches!(Day08::part1(&leaves), Err(Error::InvalidInput(_))));
+}
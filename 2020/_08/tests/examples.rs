@@ -0,0 +1 @@
+util::example_tests!(_08::Day08);
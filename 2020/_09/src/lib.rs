@@ -0,0 +1,84 @@
+use std::cmp::Ordering;
+use util::{parse_lines, Answer, Error, Result, Solution};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input<'a> = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Vec<i64>> {
+        parse_lines(input, str::parse::<i64>)
+    }
+
+    fn part1(numbers: &Vec<i64>) -> Result<Answer> {
+        Ok(solve_first(numbers)?.into())
+    }
+
+    fn part2(numbers: &Vec<i64>) -> Result<Answer> {
+        Ok(solve_second(numbers, solve_first(numbers)?)?.into())
+    }
+}
+
+fn solve_first(numbers: &[i64]) -> Result<i64> {
+    let mut i = 25;
+    loop {
+        if i >= numbers.len() {
+            break Err(Error::no_solution(
+                "every number is the sum of two of the 25 numbers before it",
+            ));
+        }
+        let mut v = numbers[(i - 25)..i].to_vec();
+        v.sort_unstable();
+        v.dedup();
+
+        let (mut bottom, mut top) = (0_usize, v.len() - 1);
+        let found = loop {
+            if top - bottom < 1 {
+                break None;
+            }
+            let (low, high) = (v[bottom], v[top]);
+            match numbers[i].cmp(&(low + high)) {
+                Ordering::Greater => bottom += 1,
+                Ordering::Equal => break Some(low + high),
+                Ordering::Less => top -= 1,
+            }
+        };
+
+        if found.is_none() {
+            break Ok(numbers[i]);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+fn solve_second(numbers: &[i64], to_find: i64) -> Result<i64> {
+    let (mut low, mut high) = (0, 2);
+    loop {
+        if high > numbers.len() {
+            // No contiguous range starting at `low` sums to the number
+            low += 1;
+            high = low + 2;
+            if high > numbers.len() {
+                break Err(Error::no_solution(format!(
+                    "no contiguous range of numbers sums to {}",
+                    to_find
+                )));
+            }
+        }
+
+        let sum = numbers[low..high].iter().sum::<i64>();
+        match sum.cmp(&to_find) {
+            Ordering::Less => high += 1,
+            Ordering::Equal => {
+                let mut sorted = numbers[low..high].to_vec();
+                sorted.sort_unstable();
+                break Ok(sorted[0] + sorted[sorted.len() - 1]);
+            }
+            Ordering::Greater => {
+                low += 1;
+                high = low + 2;
+            }
+        }
+    }
+}
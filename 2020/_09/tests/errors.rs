@@ -0,0 +1,13 @@
+use util::{Error, Solution};
+use _09::Day09;
+
+#[test]
+fn reports_no_invalid_number_instead_of_panicking() {
+    // 26 is 1 + 25, so every number after the preamble is valid
+    let numbers = (1..=26).collect::<Vec<i64>>();
+    assert!(matches!(Day09::part1(&numbers), Err(Error::NoSolution(_))));
+    assert!(matches!(Day09::part2(&numbers), Err(Error::NoSolution(_))));
+
+    let short = (1..=10).collect::<Vec<i64>>();
+    assert!(matches!(Day09::part1(&short), Err(Error::NoSolution(_))));
+}
@@ -0,0 +1 @@
+util::example_tests!(_09::Day09);
@@ -0,0 +1,48 @@
+use util::{parse_lines, Answer, Result, Solution};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    /// The adapter ratings sorted in ascending order, including the charging outlet (0) and the
+    /// device's built-in adapter (3 higher than the highest rated adapter).
+    type Input<'a> = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Vec<usize>> {
+        let mut ratings = parse_lines(input, str::parse::<usize>)?;
+        ratings.push(0);
+        ratings.sort_unstable();
+        ratings.push(ratings.last().unwrap() + 3);
+        Ok(ratings)
+    }
+
+    fn part1(ratings: &Vec<usize>) -> Result<Answer> {
+        let (mut ones, mut threes) = (0_usize, 0_usize);
+        ratings
+            .iter()
+            .zip(&ratings[1..])
+            .for_each(|(cur, next)| match next - cur {
+                1 => ones += 1,
+                3 => threes += 1,
+                _ => (),
+            });
+        Ok((ones * threes).into())
+    }
+
+    fn part2(ratings: &Vec<usize>) -> Result<Answer> {
+        Ok(count_arrangements(ratings).into())
+    }
+}
+
+fn count_arrangements(ratings: &[usize]) -> u64 {
+    // arrangements[i] holds the number of distinct ways to reach ratings[i] from the outlet. Since
+    // the ratings are sorted, only the (at most three) preceding adapters can connect to it.
+    let mut arrangements = vec![0_u64; ratings.len()];
+    arrangements[0] = 1;
+    for i in 1..ratings.len() {
+        arrangements[i] = (i.saturating_sub(3)..i)
+            .filter(|&j| ratings[i] - ratings[j] <= 3)
+            .map(|j| arrangements[j])
+            .sum();
+    }
+    arrangements.last().copied().unwrap()
+}
@@ -1,22 +1,10 @@
-use util::{read_lines, Result};
+use util::{Result, Solution};
+use _10::Day10;
 
 fn main() -> Result<()> {
-    let mut ratings = read_lines("_10/input.txt")?
-        .map(|rating| rating.parse::<usize>().unwrap())
-        .collect::<Vec<_>>();
-    ratings.push(0);
-    ratings.sort_unstable();
-    ratings.push(ratings.last().unwrap() + 3);
-
-    let (mut ones, mut threes) = (0_usize, 0_usize);
-    ratings
-        .iter()
-        .zip(&ratings[1..])
-        .for_each(|(cur, next)| match next - cur {
-            1 => ones += 1,
-            3 => threes += 1,
-            _ => (),
-        });
-    println!("{}", ones * threes);
+    let input = util::input::load(2020, 10)?;
+    let ratings = Day10::parse(&input)?;
+    println!("{}", Day10::part1(&ratings)?);
+    println!("{}", Day10::part2(&ratings)?);
     Ok(())
 }
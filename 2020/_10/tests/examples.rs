@@ -0,0 +1 @@
+util::example_tests!(_10::Day10);
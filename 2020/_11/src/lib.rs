@@ -0,0 +1,124 @@
+use util::automaton::{Automaton, Neighbourhood, Outcome, Rule, Stepping};
+use util::grid::{Grid, ALL_DIRECTIONS};
+use util::{Answer, Error, Part, Result, Solution};
+
+/// Gives up on a simulation that hasn't settled after this many generations.
+const MAX_GENERATIONS: usize = 10_000;
+
+#[derive(Clone)]
+pub struct Board {
+    tiles: Grid<char>,
+}
+
+/// People take an empty seat when none of its neighbours are occupied, and leave an occupied seat
+/// once `tolerance` or more of its neighbours are occupied. Floor never changes.
+pub struct Seating {
+    tolerance: usize,
+}
+
+impl Rule for Seating {
+    type Cell = char;
+
+    fn is_live(&self, tile: &char) -> bool {
+        *tile == '#'
+    }
+
+    fn next(&self, tile: &char, occupied_neighbours: usize) -> char {
+        match tile {
+            'L' if occupied_neighbours == 0 => '#',
+            '#' if occupied_neighbours >= self.tolerance => 'L',
+            _ => *tile,
+        }
+    }
+
+    fn is_fixed(&self, tile: &char) -> bool {
+        *tile == '.'
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = Board;
+
+    fn parse(input: &str) -> Result<Board> {
+        let tiles = Grid::parse(input, |tile| match tile {
+            'L' | '.' | '#' => Ok(tile),
+            _ => Err(format!("unknown tile '{}'", tile)),
+        })?;
+        Ok(Board { tiles })
+    }
+
+    fn part1(board: &Board) -> Result<Answer> {
+        Ok(solution_1(board)?.into())
+    }
+
+    fn part2(board: &Board) -> Result<Answer> {
+        Ok(solution_2(board)?.into())
+    }
+}
+
+fn solution_1(board: &Board) -> Result<usize> {
+    settle(simulation(board, Part::One).with_stepping(Stepping::Incremental))
+}
+
+fn solution_2(board: &Board) -> Result<usize> {
+    settle(simulation(board, Part::Two).with_stepping(Stepping::Incremental))
+}
+
+/// Sets up the seating simulation for a part: part 1 looks at adjacent seats and tolerates up to 3
+/// occupied neighbours, while part 2 looks at the first seat in each direction and tolerates 4.
+/// The simulation steps every seat each generation until told to step some other way.
+pub fn simulation(board: &Board, part: Part) -> Automaton<Seating> {
+    let (neighbourhood, tolerance) = match part {
+        Part::One => (Neighbourhood::moore(&board.tiles), 4),
+        // Seats never move, so which seats each seat can see is worked out once, up front
+        Part::Two => {
+            let visible = board
+                .tiles
+                .visibility_table(&ALL_DIRECTIONS, None, |&tile| tile != '.');
+            (Neighbourhood::from_table(&visible), 5)
+        }
+    };
+    Automaton::new(neighbourhood, board.tiles.clone(), Seating { tolerance })
+}
+
+/// Runs the seating simulation until nobody moves, returning the number of occupied seats.
+pub fn settle(mut seating: Automaton<Seating>) -> Result<usize> {
+    match seating.run(MAX_GENERATIONS) {
+        Outcome::Stable { .. } => Ok(seating
+            .cells()
+            .cells()
+            .iter()
+            .filter(|&&tile| tile == '#')
+            .count()),
+        Outcome::Cycle { start, period } => Err(Error::no_solution(format!(
+            "the seating never settles, repeating every {} generations from generation {}",
+            period, start
+        ))),
+        Outcome::Limit => Err(Error::no_solution(format!(
+            "the seating hasn't settled after {} generations",
+            MAX_GENERATIONS
+        ))),
+    }
+}
+
+/// Runs the seating simulation until nobody moves, like [`settle`], but shows every generation
+/// along the way by passing it to `show`, starting with the initial layout. Returns the
+/// generation at which the seating settled.
+pub fn replay<F>(mut seating: Automaton<Seating>, mut show: F) -> Result<usize>
+where
+    F: FnMut(usize, &Grid<char>) -> Result<()>,
+{
+    show(seating.generation(), seating.cells())?;
+    while seating.step() > 0 {
+        if seating.generation() > MAX_GENERATIONS {
+            return Err(Error::no_solution(format!(
+                "the seating hasn't settled after {} generations",
+                MAX_GENERATIONS
+            )));
+        }
+        show(seating.generation(), seating.cells())?;
+    }
+    Ok(seating.generation())
+}
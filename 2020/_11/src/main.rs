@@ -1,281 +1,179 @@
-use std::collections::HashSet;
-use util::{read_lines, Result};
-
-struct Board {
-    tiles: Vec<char>,
-    height: usize,
-    width: usize,
+use std::env;
+use std::io::Write;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+use util::automaton::Stepping;
+use util::grid::Grid;
+use util::input::Locator;
+use util::{Error, Part, Result, Solution};
+use _11::Day11;
+
+const USAGE: &str = "usage: _11 [<input file> | -] [--stepping full|incremental|parallel] \
+                     [--threads <n>] [--visualize [--delay <ms>] | --frames <dir>]";
+
+/// How long each generation stays on screen when visualizing, unless `--delay` says otherwise.
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+/// How each generation is worked out from the last.
+#[derive(Clone, Copy)]
+enum Mode {
+    Sequential(Stepping),
+    Parallel { threads: usize },
 }
 
-impl Board {
-    fn new(tiles: Vec<Vec<char>>) -> Self {
-        let height = tiles.len();
-        let width = tiles[0].len();
-        let tiles = tiles.into_iter().flatten().collect::<Vec<_>>();
-        Board {
-            tiles,
-            height,
-            width,
-        }
-    }
-
-    fn index_for(&self, x: usize, y: usize) -> usize {
-        (x % self.width) + (y * self.width)
-    }
-
-    fn remove_person(&mut self, x: usize, y: usize) {
-        let index = self.index_for(x, y);
-        self.tiles[index] = 'L';
-    }
-
-    fn place_person(&mut self, x: usize, y: usize) {
-        let index = self.index_for(x, y);
-        self.tiles[index] = '#';
-    }
-
-    fn tile_at(&self, x: usize, y: usize) -> char {
-        self.tiles[self.index_for(x, y)]
-    }
-
-    fn is_occupied_seat(&self, x: usize, y: usize) -> bool {
-        self.tile_at(x, y) == '#'
-    }
-
-    fn is_empty_seat(&self, x: usize, y: usize) -> bool {
-        self.tile_at(x, y) == 'L'
-    }
-
-    fn is_occupied(&self, x: usize, y: usize) -> bool {
-        match self.tile_at(x, y) {
-            'L' | '.' => false,
-            '#' => true,
-            _ => unimplemented!(),
-        }
-    }
-
-    fn num_adjacent_occupied(&self, x: usize, y: usize) -> usize {
-        (y > 0 && self.is_occupied(x, y - 1)) as usize
-            + (y > 0 && x < self.width - 1 && self.is_occupied(x + 1, y - 1)) as usize
-            + (x < self.width - 1 && self.is_occupied(x + 1, y)) as usize
-            + (x < self.width - 1 && y < self.height - 1 && self.is_occupied(x + 1, y + 1)) as usize
-            + (y < self.height - 1 && self.is_occupied(x, y + 1)) as usize
-            + (y < self.height - 1 && x > 0 && self.is_occupied(x - 1, y + 1)) as usize
-            + (x > 0 && self.is_occupied(x - 1, y)) as usize
-            + (x > 0 && y > 0 && self.is_occupied(x - 1, y - 1)) as usize
-    }
-
-    fn num_visible_occupied(&self, x: usize, y: usize) -> usize {
-        self.occupied_visible_n(x, y) as usize
-            + self.occupied_visible_ne(x, y) as usize
-            + self.occupied_visible_e(x, y) as usize
-            + self.occupied_visible_se(x, y) as usize
-            + self.occupied_visible_s(x, y) as usize
-            + self.occupied_visible_sw(x, y) as usize
-            + self.occupied_visible_w(x, y) as usize
-            + self.occupied_visible_nw(x, y) as usize
-    }
+/// Where each generation of the simulation is shown, if anywhere.
+enum Output {
+    Solutions,
+    /// Redraws the terminal with each generation, pausing between them.
+    Terminal {
+        delay: Duration,
+    },
+    /// Writes each generation to a numbered text file in a directory.
+    Frames {
+        dir: PathBuf,
+    },
+}
 
-    fn occupied_visible_nw(&self, mut x: usize, mut y: usize) -> bool {
-        loop {
-            if x > 0 && y > 0 {
-                x -= 1;
-                y -= 1;
-                if self.is_empty_seat(x, y) {
-                    break false;
-                } else if self.is_occupied_seat(x, y) {
-                    break true;
-                }
-            } else {
-                break false;
+fn main() -> Result<()> {
+    let mut locator = Locator::from_env();
+    let (mut stepping, mut threads) = (None, None);
+    let (mut visualize, mut delay, mut frames) = (false, None, None);
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--stepping" => {
+                stepping = Some(match args.next().as_deref() {
+                    Some("full") => "full",
+                    Some("incremental") => "incremental",
+                    Some("parallel") => "parallel",
+                    _ => {
+                        return Err(Error::usage(
+                            "`--stepping` expects one of full, incremental or parallel",
+                        ))
+                    }
+                })
             }
-        }
-    }
-
-    fn occupied_visible_w(&self, mut x: usize, y: usize) -> bool {
-        loop {
-            if x > 0 {
-                x -= 1;
-                if self.is_empty_seat(x, y) {
-                    break false;
-                } else if self.is_occupied_seat(x, y) {
-                    break true;
-                }
-            } else {
-                break false;
+            "--threads" => {
+                let count = args
+                    .next()
+                    .and_then(|threads| threads.parse::<usize>().ok())
+                    .filter(|&threads| threads > 0)
+                    .ok_or_else(|| Error::usage("`--threads` expects a number of threads"))?;
+                threads = Some(count);
             }
-        }
-    }
-
-    fn occupied_visible_sw(&self, mut x: usize, mut y: usize) -> bool {
-        loop {
-            if x > 0 && y < self.height - 1 {
-                x -= 1;
-                y += 1;
-                if self.is_empty_seat(x, y) {
-                    break false;
-                } else if self.is_occupied_seat(x, y) {
-                    break true;
-                }
-            } else {
-                break false;
+            "--visualize" => visualize = true,
+            "--delay" => {
+                let millis = args
+                    .next()
+                    .and_then(|millis| millis.parse::<u64>().ok())
+                    .ok_or_else(|| Error::usage("`--delay` expects a number of milliseconds"))?;
+                delay = Some(Duration::from_millis(millis));
             }
-        }
-    }
-
-    fn occupied_visible_s(&self, x: usize, mut y: usize) -> bool {
-        loop {
-            if y < self.height - 1 {
-                y += 1;
-                if self.is_empty_seat(x, y) {
-                    break false;
-                } else if self.is_occupied_seat(x, y) {
-                    break true;
-                }
-            } else {
-                break false;
+            "--frames" => {
+                let dir = args
+                    .next()
+                    .ok_or_else(|| Error::usage("`--frames` expects a directory"))?;
+                frames = Some(PathBuf::from(dir));
             }
-        }
-    }
-
-    fn occupied_visible_se(&self, mut x: usize, mut y: usize) -> bool {
-        loop {
-            if x < self.width - 1 && y < self.height - 1 {
-                x += 1;
-                y += 1;
-                if self.is_empty_seat(x, y) {
-                    break false;
-                } else if self.is_occupied_seat(x, y) {
-                    break true;
-                }
-            } else {
-                break false;
+            _ if arg.starts_with("--") => {
+                return Err(Error::usage(format!("unknown flag `{}`\n{}", arg, USAGE)))
             }
-        }
-    }
-
-    fn occupied_visible_n(&self, x: usize, mut y: usize) -> bool {
-        loop {
-            if y > 0 {
-                y -= 1;
-                if self.is_empty_seat(x, y) {
-                    break false;
-                } else if self.is_occupied_seat(x, y) {
-                    break true;
-                }
-            } else {
-                break false;
+            _ if locator.has_explicit_input() => {
+                return Err(Error::usage(format!(
+                    "unexpected argument `{}`\n{}",
+                    arg, USAGE
+                )))
             }
+            _ => locator = locator.input(arg),
         }
     }
 
-    fn occupied_visible_ne(&self, mut x: usize, mut y: usize) -> bool {
-        loop {
-            if y > 0 && x < self.width - 1 {
-                y -= 1;
-                x += 1;
-                if self.is_empty_seat(x, y) {
-                    break false;
-                } else if self.is_occupied_seat(x, y) {
-                    break true;
-                }
-            } else {
-                break false;
-            }
-        }
-    }
-
-    fn occupied_visible_e(&self, mut x: usize, y: usize) -> bool {
-        loop {
-            if x < self.width - 1 {
-                x += 1;
-                if self.is_empty_seat(x, y) {
-                    break false;
-                } else if self.is_occupied_seat(x, y) {
-                    break true;
-                }
-            } else {
-                break false;
-            }
+    // `--threads` on its own implies parallel stepping, which otherwise uses every available core
+    let mode = match (stepping, threads) {
+        (None, None) | (Some("incremental"), None) => Mode::Sequential(Stepping::Incremental),
+        (Some("full"), None) => Mode::Sequential(Stepping::Full),
+        (None, Some(threads)) | (Some("parallel"), Some(threads)) => Mode::Parallel { threads },
+        (Some("parallel"), None) => Mode::Parallel {
+            threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+        },
+        _ => {
+            return Err(Error::usage(format!(
+                "`--threads` only applies to parallel stepping\n{}",
+                USAGE
+            )))
         }
-    }
-
-    fn num_occupied(&self) -> usize {
-        self.tiles
-            .iter()
-            .copied()
-            .filter(|tile| tile == &'#')
-            .count()
-    }
-}
-
-fn main() -> Result<()> {
-    let input = read_lines("_11/input.txt")?
-        .map(|line| line.chars().collect::<Vec<_>>())
-        .collect::<Vec<Vec<_>>>();
-
-    let mut board = Board::new(input.clone());
-    let mut to_remove = HashSet::<(usize, usize)>::default();
-    let mut to_add = HashSet::<(usize, usize)>::default();
-
-    loop {
-        for y in 0..board.height {
-            for x in 0..board.width {
-                if board.is_empty_seat(x, y) && board.num_adjacent_occupied(x, y) == 0 {
-                    to_add.insert((x, y));
-                } else if board.is_occupied_seat(x, y) && board.num_adjacent_occupied(x, y) >= 4 {
-                    to_remove.insert((x, y));
-                }
-            }
+    };
+
+    let output = match (visualize, delay, frames) {
+        (false, None, None) => Output::Solutions,
+        (true, delay, None) => Output::Terminal {
+            delay: delay.unwrap_or(DEFAULT_DELAY),
+        },
+        (false, None, Some(dir)) => Output::Frames { dir },
+        (false, Some(_), None) => {
+            return Err(Error::usage(format!(
+                "`--delay` only applies with `--visualize`\n{}",
+                USAGE
+            )))
         }
-
-        if to_remove.is_empty() && to_add.is_empty() {
-            break;
+        _ => {
+            return Err(Error::usage(format!(
+                "`--frames` can't be combined with `--visualize` or `--delay`\n{}",
+                USAGE
+            )))
         }
-
-        to_add
-            .iter()
-            .copied()
-            .for_each(|(x, y)| board.place_person(x, y));
-        to_remove
-            .iter()
-            .copied()
-            .for_each(|(x, y)| board.remove_person(x, y));
-
-        to_remove.clear();
-        to_add.clear();
-    }
-
-    println!("solution 1: {}", board.num_occupied());
-
-    let mut board = Board::new(input);
-
-    loop {
-        for y in 0..board.height {
-            for x in 0..board.width {
-                if board.is_empty_seat(x, y) && board.num_visible_occupied(x, y) == 0 {
-                    to_add.insert((x, y));
-                } else if board.is_occupied_seat(x, y) && board.num_visible_occupied(x, y) >= 5 {
-                    to_remove.insert((x, y));
-                }
+    };
+
+    let input = locator.read(2020, 11)?;
+    let board = Day11::parse(&input)?;
+    if let Output::Frames { dir } = &output {
+        std::fs::create_dir_all(dir)?;
+    }
+    for (n, &part) in Part::BOTH.iter().enumerate() {
+        let start = Instant::now();
+        let seating = match mode {
+            Mode::Sequential(stepping) => _11::simulation(&board, part).with_stepping(stepping),
+            Mode::Parallel { threads } => {
+                _11::simulation(&board, part).with_parallel_stepping(threads)
             }
-        }
-
-        if to_remove.is_empty() && to_add.is_empty() {
-            break;
-        }
-
-        to_add
-            .iter()
-            .copied()
-            .for_each(|(x, y)| board.place_person(x, y));
-        to_remove
-            .iter()
-            .copied()
-            .for_each(|(x, y)| board.remove_person(x, y));
-
-        to_remove.clear();
-        to_add.clear();
+        };
+        let settled = match &output {
+            Output::Solutions => {
+                let occupied = _11::settle(seating)?;
+                println!(
+                    "solution {}: {} (in {:.2?})",
+                    n + 1,
+                    occupied,
+                    start.elapsed()
+                );
+                continue;
+            }
+            Output::Terminal { delay } => _11::replay(seating, |generation, tiles| {
+                draw(part, generation, tiles)?;
+                thread::sleep(*delay);
+                Ok(())
+            })?,
+            Output::Frames { dir } => _11::replay(seating, |generation, tiles| {
+                let path = dir.join(format!("part{}-{:04}.txt", part, generation));
+                std::fs::write(path, format!("{}\n", tiles))?;
+                Ok(())
+            })?,
+        };
+        println!("part {} settled after {} generations", part, settled);
     }
-    println!("solution 2: {}", board.num_occupied());
+    Ok(())
+}
+
+/// Clears the terminal and draws a generation of the seating, with a caption saying which it is.
+fn draw(part: Part, generation: usize, tiles: &Grid<char>) -> Result<()> {
+    let occupied = tiles.cells().iter().filter(|&&tile| tile == '#').count();
+    let mut stdout = std::io::stdout().lock();
+    write!(
+        stdout,
+        "\x1b[2J\x1b[H{}\npart {}, generation {}, {} occupied\n",
+        tiles, part, generation, occupied
+    )?;
+    stdout.flush()?;
     Ok(())
 }
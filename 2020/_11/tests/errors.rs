@@ -0,0 +1,11 @@
+use util::{Error, Solution};
+use _11::Day11;
+
+#[test]
+fn reports_an_unknown_tile_instead_of_panicking() {
+    match Day11::parse("L.L\nL?L\n") {
+        Err(Error::Parse { line, column, .. }) => assert_eq!((line, column), (2, 2)),
+        Err(err) => panic!("expected a parse error, got {}", err),
+        Ok(_) => panic!("expected a parse error"),
+    }
+}
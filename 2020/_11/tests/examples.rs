@@ -0,0 +1 @@
+util::example_tests!(_11::Day11);
@@ -0,0 +1,147 @@
+use util::automaton::{Outcome, Stepping};
+use util::testing::Rng;
+use util::{Part, Solution};
+use _11::{Board, Day11};
+
+#[test]
+fn incremental_stepping_matches_full_stepping() {
+    let input = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/examples/test_input.txt"
+    ))
+    .unwrap();
+    let board = Day11::parse(&input).unwrap();
+
+    for &part in &Part::BOTH {
+        let mut full = _11::simulation(&board, part);
+        let mut incremental = _11::simulation(&board, part).with_stepping(Stepping::Incremental);
+        loop {
+            let changed = full.step();
+            assert_eq!(incremental.step(), changed);
+            assert_eq!(incremental.generation(), full.generation());
+            assert_eq!(
+                incremental.cells(),
+                full.cells(),
+                "part {} differs at generation {}",
+                part,
+                full.generation()
+            );
+            if changed == 0 {
+                break;
+            }
+        }
+        assert!(full.generation() > 1);
+
+        let outcome = _11::simulation(&board, part).run(100);
+        assert!(matches!(outcome, Outcome::Stable { .. }));
+        assert_eq!(
+            _11::simulation(&board, part)
+                .with_stepping(Stepping::Incremental)
+                .run(100),
+            outcome
+        );
+    }
+}
+
+#[test]
+fn parallel_stepping_settles_the_example_like_sequential_stepping() {
+    let input = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/examples/test_input.txt"
+    ))
+    .unwrap();
+    let board = Day11::parse(&input).unwrap();
+
+    for &part in &Part::BOTH {
+        for &threads in &[1, 2, 3, 16] {
+            let mut sequential = _11::simulation(&board, part);
+            let mut parallel = _11::simulation(&board, part).with_parallel_stepping(threads);
+            loop {
+                let changed = sequential.step();
+                assert_eq!(parallel.step(), changed);
+                assert_eq!(parallel.generation(), sequential.generation());
+                assert_eq!(
+                    parallel.cells(),
+                    sequential.cells(),
+                    "part {} with {} threads differs at generation {}",
+                    part,
+                    threads,
+                    sequential.generation()
+                );
+                if changed == 0 {
+                    break;
+                }
+            }
+            assert!(sequential.generation() > 1);
+            assert_eq!(
+                _11::settle(_11::simulation(&board, part).with_parallel_stepping(threads)).unwrap(),
+                _11::settle(_11::simulation(&board, part)).unwrap()
+            );
+        }
+    }
+}
+
+/// A large pseudo-random seat layout, the same every time.
+fn synthetic_board(width: usize, height: usize) -> Board {
+    let mut rng = Rng::new(11);
+    let mut layout = String::new();
+    for _ in 0..height {
+        for _ in 0..width {
+            layout.push(if rng.chance(1, 5) { '.' } else { 'L' });
+        }
+        layout.push('\n');
+    }
+    Day11::parse(&layout).unwrap()
+}
+
+#[test]
+fn parallel_stepping_matches_sequential_stepping() {
+    // Random layouts don't always settle, so compare a fixed number of generations
+    let board = synthetic_board(97, 61);
+    for &part in &Part::BOTH {
+        let mut sequential = _11::simulation(&board, part);
+        let mut parallel = [1, 3, 8, 100]
+            .iter()
+            .map(|&threads| _11::simulation(&board, part).with_parallel_stepping(threads))
+            .collect::<Vec<_>>();
+        for _ in 0..40 {
+            let changed = sequential.step();
+            for parallel in &mut parallel {
+                assert_eq!(parallel.step(), changed);
+                assert_eq!(parallel.cells(), sequential.cells());
+            }
+        }
+        assert!(sequential.generation() > 1);
+    }
+}
+
+#[test]
+fn replay_shows_every_generation_until_the_seating_settles() {
+    let input = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/examples/test_input.txt"
+    ))
+    .unwrap();
+    let board = Day11::parse(&input).unwrap();
+
+    let mut frames = Vec::new();
+    let settled = _11::replay(
+        _11::simulation(&board, Part::One).with_stepping(Stepping::Incremental),
+        |generation, tiles| {
+            frames.push((generation, tiles.to_string()));
+            Ok(())
+        },
+    )
+    .unwrap();
+
+    assert_eq!(settled, 5);
+    assert_eq!(
+        frames
+            .iter()
+            .map(|&(generation, _)| generation)
+            .collect::<Vec<_>>(),
+        (0..=5).collect::<Vec<_>>()
+    );
+    assert_eq!(frames[0].1, input.trim_end());
+    assert_eq!(frames[5].1.matches('#').count(), 37);
+}
@@ -0,0 +1,177 @@
+use util::{parse_lines, Answer, LineError, Result, Solution};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Turn {
+    Right,
+    Left,
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub enum Direction {
+    North,
+    #[default]
+    East,
+    South,
+    West,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Action {
+    Move(Direction),
+    Turn(Turn),
+    Forward,
+}
+
+#[derive(Default)]
+struct Ship {
+    facing: Direction,
+    x: i32,
+    y: i32,
+}
+
+struct Waypoint {
+    rel_x: i32,
+    rel_y: i32,
+}
+
+impl Default for Waypoint {
+    fn default() -> Self {
+        Waypoint {
+            rel_x: 10,
+            rel_y: 1,
+        }
+    }
+}
+
+#[derive(Default)]
+struct World {
+    ship: Ship,
+    waypoint: Waypoint,
+}
+
+impl World {
+    fn move_ship_toward_waypoint(&mut self, amount: u16) {
+        self.ship.x += i32::from(amount) * self.waypoint.rel_x;
+        self.ship.y += i32::from(amount) * self.waypoint.rel_y;
+    }
+
+    fn move_waypoint(&mut self, direction: Direction, amount: u16) {
+        let amount = i32::from(amount);
+        match direction {
+            Direction::North => self.waypoint.rel_y += amount,
+            Direction::East => self.waypoint.rel_x += amount,
+            Direction::South => self.waypoint.rel_y -= amount,
+            Direction::West => self.waypoint.rel_x -= amount,
+        }
+    }
+
+    fn rotate_waypoint(&mut self, turn: Turn, degrees: u16) {
+        let theta = (std::f64::consts::PI / 2.0)
+            * f64::from(degrees / 90)
+            * if turn == Turn::Left { 1.0 } else { -1.0 };
+        let (ship_x, ship_y) = (f64::from(self.ship.x), f64::from(self.ship.y));
+        let (my_x, my_y) = (
+            f64::from(self.waypoint.rel_x) + ship_x,
+            f64::from(self.waypoint.rel_y) + ship_y,
+        );
+        self.waypoint.rel_x =
+            (theta.cos() * (my_x - ship_x) - theta.sin() * (my_y - ship_y)).round() as i32;
+        self.waypoint.rel_y =
+            (theta.sin() * (my_x - ship_x) + theta.cos() * (my_y - ship_y)).round() as i32;
+    }
+}
+
+impl Ship {
+    fn turn(&mut self, turn: Turn, degrees: u16) {
+        let turns = degrees / 90;
+        for _ in 0..turns {
+            match turn {
+                Turn::Left => match self.facing {
+                    Direction::North => self.facing = Direction::West,
+                    Direction::East => self.facing = Direction::North,
+                    Direction::South => self.facing = Direction::East,
+                    Direction::West => self.facing = Direction::South,
+                },
+                Turn::Right => match self.facing {
+                    Direction::North => self.facing = Direction::East,
+                    Direction::East => self.facing = Direction::South,
+                    Direction::South => self.facing = Direction::West,
+                    Direction::West => self.facing = Direction::North,
+                },
+            }
+        }
+    }
+
+    fn forward(&mut self, amount: u16) {
+        self.travel(self.facing, amount);
+    }
+
+    fn travel(&mut self, direction: Direction, amount: u16) {
+        match direction {
+            Direction::North => self.y += i32::from(amount),
+            Direction::East => self.x += i32::from(amount),
+            Direction::South => self.y -= i32::from(amount),
+            Direction::West => self.x -= i32::from(amount),
+        }
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    /// The navigation instructions, as an action and its value.
+    type Input<'a> = Vec<(Action, u16)>;
+
+    fn parse(input: &str) -> Result<Vec<(Action, u16)>> {
+        parse_lines(input, |line| {
+            let mut chars = line.chars();
+            let action = match chars.next() {
+                Some('N') => Action::Move(Direction::North),
+                Some('S') => Action::Move(Direction::South),
+                Some('E') => Action::Move(Direction::East),
+                Some('W') => Action::Move(Direction::West),
+                Some('L') => Action::Turn(Turn::Left),
+                Some('R') => Action::Turn(Turn::Right),
+                Some('F') => Action::Forward,
+                Some(other) => return Err(LineError::at(0, format!("unknown action '{}'", other))),
+                None => return Err(LineError::at(0, "missing action")),
+            };
+            let value_start = line.len() - chars.as_str().len();
+            let value = chars
+                .as_str()
+                .parse::<u16>()
+                .map_err(|err| LineError::at(value_start, format!("invalid value: {}", err)))?;
+            if matches!(action, Action::Turn(_)) && value % 90 != 0 {
+                return Err(LineError::at(
+                    value_start,
+                    format!("{} degrees is not a multiple of 90", value),
+                ));
+            }
+            Ok((action, value))
+        })
+    }
+
+    fn part1(instructions: &Vec<(Action, u16)>) -> Result<Answer> {
+        let mut ship = Ship::default();
+        for (action, value) in instructions.iter().copied() {
+            match action {
+                Action::Move(direction) => ship.travel(direction, value),
+                Action::Turn(turn) => ship.turn(turn, value),
+                Action::Forward => ship.forward(value),
+            }
+        }
+        Ok((ship.x.abs() + ship.y.abs()).into())
+    }
+
+    fn part2(instructions: &Vec<(Action, u16)>) -> Result<Answer> {
+        let mut world = World::default();
+        for (action, value) in instructions.iter().copied() {
+            match action {
+                Action::Move(direction) => world.move_waypoint(direction, value),
+                Action::Turn(turn) => world.rotate_waypoint(turn, value),
+                Action::Forward => world.move_ship_toward_waypoint(value),
+            }
+        }
+        Ok((world.ship.x.abs() + world.ship.y.abs()).into())
+    }
+}
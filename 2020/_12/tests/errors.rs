@@ -0,0 +1,14 @@
+use util::{Error, Solution};
+use _12::Day12;
+
+#[test]
+fn reports_an_unknown_action_instead_of_panicking() {
+    match Day12::parse("F10\nX3\n") {
+        Err(Error::Parse { line, message, .. }) => {
+            assert_eq!(line, 2);
+            assert_eq!(message, "unknown action 'X'");
+        }
+        Err(err) => panic!("expected a parse error, got {}", err),
+        Ok(_) => panic!("expected a parse error"),
+    }
+}
@@ -0,0 +1 @@
+util::example_tests!(_12::Day12);
@@ -0,0 +1,280 @@
+use std::path::PathBuf;
+use util::bench;
+use util::{Error, Part, Result};
+
+pub const USAGE: &str = "\
+usage: aoc run <year> [<day> | <first>-<last> | all] [options]
+       aoc fetch <year> [<day> | <first>-<last> | all] [--input-dir <dir>]
+       aoc submit <year> <day> <1|2> [<answer>] [--input-dir <dir>] [--input <file|->]
+
+run options:
+    --part <1|2>        only solve the given part
+    --input-dir <dir>   look for inputs under <dir> (defaults to $AOC_INPUT_DIR)
+    --input <file|->    read the input from <file>, or stdin if `-` (single day only)
+    --bench             time parsing and each part instead of printing answers
+    --warmup <n>        untimed runs of each phase before timing (default 3)
+    --iterations <n>    timed runs of each phase (default 10)
+    --json              print benchmark results as JSON
+
+fetch downloads inputs that aren't already cached, authenticating with the session cookie in
+$AOC_SESSION or ~/.config/aoc/session.
+
+submit posts an answer, solving the part if no answer is given. Accepted answers are recorded in
+<year>/answers.toml, and later runs flag any answer that no longer matches.
+
+examples:
+    aoc run 2020 7 --part 2
+    aoc run 2020 1-5
+    aoc run 2020 --bench --iterations 100 --json
+    aoc fetch 2020 1-12
+    aoc submit 2020 7 2";
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Command {
+    Run {
+        year: u16,
+        first: u8,
+        last: u8,
+        parts: Vec<Part>,
+        input_dir: Option<PathBuf>,
+        input: Option<PathBuf>,
+        /// Benchmark rather than print answers
+        bench: Option<bench::Options>,
+        json: bool,
+    },
+    Fetch {
+        year: u16,
+        first: u8,
+        last: u8,
+        input_dir: Option<PathBuf>,
+    },
+    Submit {
+        year: u16,
+        day: u8,
+        part: Part,
+        /// Submit this answer rather than solving the part
+        answer: Option<String>,
+        input_dir: Option<PathBuf>,
+        input: Option<PathBuf>,
+    },
+}
+
+pub fn parse<I>(args: I) -> Result<Command>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        Some("run") => parse_run(args),
+        Some("fetch") => parse_fetch(args),
+        Some("submit") => parse_submit(args),
+        Some(other) => Err(Error::usage(format!(
+            "unknown command `{}`\n\n{}",
+            other, USAGE
+        ))),
+        None => Err(Error::usage(USAGE)),
+    }
+}
+
+fn parse_run(mut args: impl Iterator<Item = String>) -> Result<Command> {
+    let mut positional = Vec::new();
+    let mut parts = Part::BOTH.to_vec();
+    let (mut input_dir, mut input) = (None, None);
+    let (mut bench, mut bench_options, mut json) = (false, bench::Options::default(), false);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                parts = match args.next().as_deref() {
+                    Some("1") => vec![Part::One],
+                    Some("2") => vec![Part::Two],
+                    _ => return Err(Error::usage("`--part` expects either 1 or 2")),
+                }
+            }
+            "--input-dir" => {
+                input_dir = Some(
+                    args.next()
+                        .ok_or_else(|| Error::usage("`--input-dir` expects a directory"))?
+                        .into(),
+                )
+            }
+            "--input" => {
+                input = Some(
+                    args.next()
+                        .ok_or_else(|| Error::usage("`--input` expects a file or `-`"))?
+                        .into(),
+                )
+            }
+            "--bench" => bench = true,
+            "--warmup" => bench_options.warmup = parse_count("--warmup", args.next())?,
+            "--iterations" => bench_options.iterations = parse_count("--iterations", args.next())?,
+            "--json" => json = true,
+            _ if arg.starts_with("--") => {
+                return Err(Error::usage(format!("unknown flag `{}`", arg)))
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let (year, first, last) = parse_selection(positional)?;
+    if bench_options.iterations == 0 {
+        return Err(Error::usage("`--iterations` must be at least 1"));
+    }
+    if json && !bench {
+        return Err(Error::usage("`--json` can only be used with `--bench`"));
+    }
+    if input.is_some() && first != last {
+        return Err(Error::usage(
+            "`--input` can only be used when running a single day",
+        ));
+    }
+
+    Ok(Command::Run {
+        year,
+        first,
+        last,
+        parts,
+        input_dir,
+        input,
+        bench: if bench { Some(bench_options) } else { None },
+        json,
+    })
+}
+
+fn parse_fetch(mut args: impl Iterator<Item = String>) -> Result<Command> {
+    let mut positional = Vec::new();
+    let mut input_dir = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input-dir" => {
+                input_dir = Some(
+                    args.next()
+                        .ok_or_else(|| Error::usage("`--input-dir` expects a directory"))?
+                        .into(),
+                )
+            }
+            _ if arg.starts_with("--") => {
+                return Err(Error::usage(format!("unknown flag `{}`", arg)))
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let (year, first, last) = parse_selection(positional)?;
+    Ok(Command::Fetch {
+        year,
+        first,
+        last,
+        input_dir,
+    })
+}
+
+fn parse_submit(mut args: impl Iterator<Item = String>) -> Result<Command> {
+    let mut positional = Vec::new();
+    let (mut input_dir, mut input) = (None, None);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input-dir" => {
+                input_dir = Some(
+                    args.next()
+                        .ok_or_else(|| Error::usage("`--input-dir` expects a directory"))?
+                        .into(),
+                )
+            }
+            "--input" => {
+                input = Some(
+                    args.next()
+                        .ok_or_else(|| Error::usage("`--input` expects a file or `-`"))?
+                        .into(),
+                )
+            }
+            _ if arg.starts_with("--") => {
+                return Err(Error::usage(format!("unknown flag `{}`", arg)))
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let answer = if positional.len() > 3 {
+        positional.pop()
+    } else {
+        None
+    };
+    let part = match positional.pop().as_deref() {
+        Some("1") => Part::One,
+        Some("2") => Part::Two,
+        _ => {
+            return Err(Error::usage(
+                "submit expects a year, a day and a part of 1 or 2",
+            ))
+        }
+    };
+    let (year, first, last) = parse_selection(positional)?;
+    if first != last {
+        return Err(Error::usage("submit expects a single day"));
+    }
+    if answer.is_some() && input.is_some() {
+        return Err(Error::usage(
+            "`--input` can't be used when submitting a given answer",
+        ));
+    }
+
+    Ok(Command::Submit {
+        year,
+        day: first,
+        part,
+        answer,
+        input_dir,
+        input,
+    })
+}
+
+/// Parses the positional `<year> [<days>]` arguments shared by every command.
+fn parse_selection(positional: Vec<String>) -> Result<(u16, u8, u8)> {
+    let mut positional = positional.into_iter();
+    let year = positional
+        .next()
+        .ok_or_else(|| Error::usage(USAGE))?
+        .parse::<u16>()
+        .map_err(|_| Error::usage("year must be a number"))?;
+    let (first, last) = match positional.next() {
+        None => (1, 25),
+        Some(days) => parse_days(&days)?,
+    };
+    if let Some(extra) = positional.next() {
+        return Err(Error::usage(format!("unexpected argument `{}`", extra)));
+    }
+    Ok((year, first, last))
+}
+
+fn parse_count(flag: &str, value: Option<String>) -> Result<usize> {
+    match value.as_deref().map(str::parse::<usize>) {
+        Some(Ok(count)) => Ok(count),
+        _ => Err(Error::usage(format!("`{}` expects a number", flag))),
+    }
+}
+
+/// Parses a day selection: a single day (`7`), an inclusive range (`3-9`) or `all`.
+fn parse_days(days: &str) -> Result<(u8, u8)> {
+    let parse_day = |day: &str| -> Result<u8> {
+        match day.parse::<u8>() {
+            Ok(day) if (1..=25).contains(&day) => Ok(day),
+            _ => Err(Error::usage(format!(
+                "`{}` is not a day between 1 and 25",
+                day
+            ))),
+        }
+    };
+
+    if days == "all" {
+        Ok((1, 25))
+    } else if let Some((first, last)) = days.split_once('-') {
+        let (first, last) = (parse_day(first)?, parse_day(last)?);
+        if first > last {
+            return Err(Error::usage(format!("`{}` is an empty range", days)));
+        }
+        Ok((first, last))
+    } else {
+        let day = parse_day(days)?;
+        Ok((day, day))
+    }
+}
@@ -0,0 +1,281 @@
+//! A client for the Advent of Code website.
+//!
+//! Requests are authenticated with the session cookie of a logged in user, taken from the
+//! `AOC_SESSION` environment variable or, failing that, from `~/.config/aoc/session`. The server can
+//! be overridden with `AOC_BASE_URL`, which is how the tests point the client at a local stand-in.
+
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use util::{Answer, Error, Part, Result};
+
+pub const SESSION_ENV_VAR: &str = "AOC_SESSION";
+pub const BASE_URL_ENV_VAR: &str = "AOC_BASE_URL";
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/bgourlie/adventofcode by bgourlie@gmail.com";
+
+/// The minimum time between two requests to the server, including requests made by earlier runs.
+pub const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Where a [`RateLimiter`] gets the time from, and how it waits.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The real time, waited for by sleeping the thread.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Spaces requests out by at least `min_interval`. The time of the last request is recorded in a
+/// stamp file, in milliseconds since the Unix epoch, so the limit also holds across separate runs.
+pub struct RateLimiter {
+    min_interval: Duration,
+    stamp: PathBuf,
+    clock: Box<dyn Clock>,
+}
+
+impl RateLimiter {
+    pub fn new(min_interval: Duration, stamp: impl Into<PathBuf>) -> Self {
+        RateLimiter {
+            min_interval,
+            stamp: stamp.into(),
+            clock: Box::new(SystemClock),
+        }
+    }
+
+    /// Uses `clock` instead of the system clock, so tests can control time.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Blocks until a request may be made, then records that one is being made.
+    pub fn wait(&self) -> Result<()> {
+        let last_request = std::fs::read_to_string(&self.stamp)
+            .ok()
+            .and_then(|stamp| stamp.trim().parse::<u64>().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+        if let Some(last_request) = last_request {
+            let elapsed = self
+                .clock
+                .now()
+                .duration_since(last_request)
+                .unwrap_or_default();
+            if elapsed < self.min_interval {
+                self.clock.sleep(self.min_interval - elapsed);
+            }
+        }
+
+        if let Some(parent) = self.stamp.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let now = self
+            .clock
+            .now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        std::fs::write(&self.stamp, now.as_millis().to_string())?;
+        Ok(())
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+    rate_limiter: RateLimiter,
+}
+
+impl Client {
+    pub fn new(
+        base_url: impl Into<String>,
+        session: impl Into<String>,
+        rate_limiter: RateLimiter,
+    ) -> Self {
+        Client {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            rate_limiter,
+        }
+    }
+
+    /// Creates a client configured from the environment, recording the time of each request
+    /// under `state_dir`.
+    pub fn from_env(state_dir: &Path) -> Result<Self> {
+        let base_url = env::var(BASE_URL_ENV_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        let rate_limiter = RateLimiter::new(MIN_REQUEST_INTERVAL, state_dir.join(".last-request"));
+        Ok(Client::new(base_url, session()?, rate_limiter))
+    }
+
+    pub fn fetch_input(&self, year: u16, day: u8) -> Result<String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        self.rate_limiter.wait()?;
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|err| request_error(&url, err))?;
+        Ok(response.into_string()?)
+    }
+
+    pub fn submit(&self, year: u16, day: u8, part: Part, answer: &Answer) -> Result<Verdict> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        self.rate_limiter.wait()?;
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[
+                ("level", &part.to_string()),
+                ("answer", &answer.to_string()),
+            ])
+            .map_err(|err| request_error(&url, err))?;
+        Verdict::parse(&response.into_string()?)
+            .ok_or_else(|| Error::http(format!("{} gave a response that wasn't understood", url)))
+    }
+}
+
+/// The server's judgement of a submitted answer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint as to which way.
+    Incorrect,
+    /// An answer was submitted too recently, and nothing was checked.
+    Wait(Duration),
+    /// The part has already been solved, or part 1 hasn't been solved yet.
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Recognises the verdict in the page returned after submitting an answer.
+    pub fn parse(page: &str) -> Option<Verdict> {
+        if page.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                Some(Verdict::TooHigh)
+            } else if page.contains("your answer is too low") {
+                Some(Verdict::TooLow)
+            } else {
+                Some(Verdict::Incorrect)
+            }
+        } else if page.contains("You gave an answer too recently") {
+            Some(Verdict::Wait(
+                parse_wait(page).unwrap_or(MIN_REQUEST_INTERVAL),
+            ))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Some(Verdict::WrongLevel)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::Wait(wait) => write!(
+                f,
+                "not checked, wait {}s before submitting again",
+                wait.as_secs()
+            ),
+            Verdict::WrongLevel => write!(f, "not checked, the part is already solved or locked"),
+        }
+    }
+}
+
+/// Parses the time left in a message like "You have 1m 20s left to wait".
+fn parse_wait(page: &str) -> Option<Duration> {
+    let start = page.find("You have ")? + "You have ".len();
+    let end = start + page[start..].find(" left to wait")?;
+    page[start..end]
+        .split_whitespace()
+        .map(|amount| {
+            let (value, unit) = amount.split_at(amount.len().checked_sub(1)?);
+            let value = value.parse::<u64>().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+fn request_error(url: &str, err: ureq::Error) -> Error {
+    match err {
+        ureq::Error::Status(404, _) => Error::http(format!(
+            "{} was not found; the puzzle may not be unlocked yet",
+            url
+        )),
+        ureq::Error::Status(400, _) | ureq::Error::Status(401, _) => Error::http(format!(
+            "{} rejected the session token, which may have expired",
+            url
+        )),
+        ureq::Error::Status(429, response) => Error::http(format!(
+            "{} is rate limited, retry after {} seconds",
+            url,
+            response.header("Retry-After").unwrap_or("some")
+        )),
+        ureq::Error::Status(status, _) => {
+            Error::http(format!("{} responded with status {}", url, status))
+        }
+        ureq::Error::Transport(transport) => Error::http(transport.to_string()),
+    }
+}
+
+/// Reads the session token from `AOC_SESSION`, or the `aoc/session` file in the user's config
+/// directory.
+fn session() -> Result<String> {
+    if let Some(session) = env::var(SESSION_ENV_VAR)
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+    {
+        return Ok(session.trim().to_string());
+    }
+
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")));
+    let session_file = config_dir.map(|dir| dir.join("aoc").join("session"));
+    session_file
+        .as_ref()
+        .and_then(|file| std::fs::read_to_string(file).ok())
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+        .ok_or_else(|| {
+            Error::usage(format!(
+                "No session token found, set {} or save it to {}",
+                SESSION_ENV_VAR,
+                session_file.map_or("~/.config/aoc/session".to_string(), |file| file
+                    .display()
+                    .to_string())
+            ))
+        })
+}
@@ -0,0 +1,98 @@
+//! A record of answers the puzzle server has accepted.
+//!
+//! Each year's ledger lives at `<year>/answers.toml` under the input cache root, alongside the
+//! inputs the answers were computed from:
+//!
+//! ```toml
+//! [[answer]]
+//! day = 1
+//! part = 1
+//! answer = "960075"
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use util::{Answer, Error, Part, Result};
+
+#[derive(Debug)]
+pub struct Ledger {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+struct File {
+    #[serde(default, rename = "answer")]
+    entries: Vec<Entry>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct Entry {
+    day: u8,
+    part: u8,
+    answer: String,
+}
+
+impl Ledger {
+    /// Where the ledger for `year` is kept under the input cache root `root`.
+    pub fn path(root: &Path, year: u16) -> PathBuf {
+        root.join(year.to_string()).join("answers.toml")
+    }
+
+    /// Loads the ledger at `path`, which is empty if the file doesn't exist yet.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let file = match std::fs::read_to_string(&path) {
+            Ok(contents) => toml::from_str::<File>(&contents)
+                .map_err(|err| Error::invalid_input(format!("{}: {}", path.display(), err)))?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => File::default(),
+            Err(err) => return Err(Error::read(&path, err)),
+        };
+        Ok(Ledger {
+            path,
+            entries: file.entries,
+        })
+    }
+
+    /// The verified answer for a part, if there is one.
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        let part = level(part);
+        self.entries
+            .iter()
+            .find(|entry| entry.day == day && entry.part == part)
+            .map(|entry| entry.answer.as_str())
+    }
+
+    /// Records the verified answer for a part, replacing any previous answer.
+    pub fn record(&mut self, day: u8, part: Part, answer: &Answer) {
+        let level = level(part);
+        self.entries
+            .retain(|entry| !(entry.day == day && entry.part == level));
+        self.entries.push(Entry {
+            day,
+            part: level,
+            answer: answer.to_string(),
+        });
+        self.entries.sort_by_key(|entry| (entry.day, entry.part));
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let file = File {
+            entries: self.entries.clone(),
+        };
+        let contents = toml::to_string(&file)
+            .map_err(|err| Error::invalid_input(format!("{}: {}", self.path.display(), err)))?;
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, contents)?;
+        Ok(())
+    }
+}
+
+fn level(part: Part) -> u8 {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+    }
+}
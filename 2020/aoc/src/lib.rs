@@ -0,0 +1,315 @@
+pub mod cli;
+pub mod client;
+pub mod ledger;
+pub mod registry;
+
+use cli::Command;
+use client::{Client, Verdict};
+use ledger::Ledger;
+use registry::Day;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use util::bench::{self, Phase, Stats};
+use util::input::Locator;
+use util::{Answer, Error, Part, Result};
+
+/// Runs the command described by `args`, returning the number of puzzle parts (or, when fetching,
+/// days) that failed, including parts whose answer no longer matches the verified answer in the
+/// ledger. A failing day is reported and skipped rather than stopping the remaining days
+/// from running.
+pub fn run<I>(args: I) -> Result<usize>
+where
+    I: IntoIterator<Item = String>,
+{
+    match cli::parse(args)? {
+        Command::Run {
+            year,
+            first,
+            last,
+            parts,
+            input_dir,
+            input,
+            bench,
+            json,
+        } => {
+            let mut locator = Locator::from_env();
+            if let Some(input_dir) = input_dir {
+                locator = locator.root(input_dir);
+            }
+            if let Some(input) = input {
+                locator = locator.input(input);
+            }
+
+            let days = registry::select(year, first, last).collect::<Vec<_>>();
+            if days.is_empty() {
+                return Err(Error::usage(format!(
+                    "no solutions registered for {} days {}-{}",
+                    year, first, last
+                )));
+            }
+            match bench {
+                Some(options) => bench_days(&days, &parts, &locator, &options, json),
+                None => {
+                    // Verified answers only apply to the inputs they were computed from
+                    let ledger = if locator.has_explicit_input() {
+                        None
+                    } else {
+                        Some(Ledger::load(Ledger::path(&locator.cache_root(), year))?)
+                    };
+                    Ok(days
+                        .into_iter()
+                        .map(|day| run_day(day, &parts, &locator, ledger.as_ref()))
+                        .sum())
+                }
+            }
+        }
+        Command::Fetch {
+            year,
+            first,
+            last,
+            input_dir,
+        } => {
+            let mut locator = Locator::from_env();
+            if let Some(input_dir) = input_dir {
+                locator = locator.root(input_dir);
+            }
+            let client = Client::from_env(&locator.cache_root())?;
+            Ok(fetch(&client, &locator, year, first..=last))
+        }
+        Command::Submit {
+            year,
+            day,
+            part,
+            answer,
+            input_dir,
+            input,
+        } => {
+            let mut locator = Locator::from_env();
+            if let Some(input_dir) = input_dir {
+                locator = locator.root(input_dir);
+            }
+            if let Some(input) = input {
+                locator = locator.input(input);
+            }
+
+            let answer = match answer {
+                Some(answer) => Answer::Text(answer),
+                None => solve(year, day, part, &locator)?,
+            };
+            let client = Client::from_env(&locator.cache_root())?;
+            let mut ledger = Ledger::load(Ledger::path(&locator.cache_root(), year))?;
+            let verdict = submit(&client, &mut ledger, year, day, part, &answer)?;
+            Ok(if verdict == Verdict::Correct { 0 } else { 1 })
+        }
+    }
+}
+
+fn solve(year: u16, day: u8, part: Part, locator: &Locator) -> Result<Answer> {
+    let solution = registry::find(year, day)
+        .ok_or_else(|| Error::usage(format!("no solution registered for {} day {}", year, day)))?;
+    let input = locator.read(year, day)?;
+    solution
+        .answers(&input, &[part])?
+        .pop()
+        .unwrap_or_else(|| Err(Error::no_solution("the part wasn't solved")))
+}
+
+/// Submits an answer for a part, recording it in `ledger` if it's correct. An answer that has
+/// already been verified is checked against the ledger instead of being resubmitted.
+pub fn submit(
+    client: &Client,
+    ledger: &mut Ledger,
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &Answer,
+) -> Result<Verdict> {
+    let answer_text = answer.to_string();
+    if let Some(verified) = ledger.get(day, part) {
+        return if verified == answer_text {
+            println!(
+                "{} day {:02} part {}: {} was already verified",
+                year, day, part, answer
+            );
+            Ok(Verdict::Correct)
+        } else {
+            Err(Error::invalid_input(format!(
+                "{} day {:02} part {}: {} doesn't match the verified answer {}",
+                year, day, part, answer, verified
+            )))
+        };
+    }
+
+    let verdict = client.submit(year, day, part, answer)?;
+    println!(
+        "{} day {:02} part {}: {} is {}",
+        year, day, part, answer, verdict
+    );
+    if verdict == Verdict::Correct {
+        ledger.record(day, part, answer);
+        ledger.save()?;
+    }
+    Ok(verdict)
+}
+
+/// Downloads the input for each of `days` that isn't already cached, returning how many of them
+/// failed.
+pub fn fetch(client: &Client, locator: &Locator, year: u16, days: RangeInclusive<u8>) -> usize {
+    days.filter(|&day| !fetch_day(client, locator, year, day))
+        .count()
+}
+
+/// Downloads the input for a day into the cache unless it's already there, returning whether the
+/// input is now cached. Inputs never change once published, so a cached input is never refetched.
+fn fetch_day(client: &Client, locator: &Locator, year: u16, day: u8) -> bool {
+    let path = locator.cache_path(year, day);
+    if path.exists() {
+        println!(
+            "{} day {:02}: already cached at {}",
+            year,
+            day,
+            path.display()
+        );
+        return true;
+    }
+
+    match client
+        .fetch_input(year, day)
+        .and_then(|input| write_atomically(&path, &input))
+    {
+        Ok(()) => {
+            println!("{} day {:02}: saved to {}", year, day, path.display());
+            true
+        }
+        Err(err) => {
+            eprintln!("{} day {:02}: {}", year, day, err);
+            false
+        }
+    }
+}
+
+/// Writes `contents` to a temporary file next to `path` and renames it into place, so an
+/// interrupted download never leaves a partial input behind.
+fn write_atomically(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let temp = path.with_extension("txt.partial");
+    std::fs::write(&temp, contents)?;
+    std::fs::rename(&temp, path)?;
+    Ok(())
+}
+
+/// Solves `parts` of a day, returning how many of them failed or regressed from the answer
+/// verified in `ledger`.
+fn run_day(day: &Day, parts: &[Part], locator: &Locator, ledger: Option<&Ledger>) -> usize {
+    let answers = match locator
+        .read(day.year, day.day)
+        .and_then(|input| day.answers(&input, parts))
+    {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("{} day {:02}: {}", day.year, day.day, err);
+            return parts.len();
+        }
+    };
+
+    let mut failed = 0;
+    for (part, answer) in parts.iter().zip(answers) {
+        match answer {
+            Ok(answer) => {
+                println!("{} day {:02} part {}: {}", day.year, day.day, part, answer);
+                let verified = ledger.and_then(|ledger| ledger.get(day.day, *part));
+                if let Some(verified) = verified.filter(|&verified| verified != answer.to_string())
+                {
+                    eprintln!(
+                        "{} day {:02} part {}: regression, the verified answer is {}",
+                        day.year, day.day, part, verified
+                    );
+                    failed += 1;
+                }
+            }
+            Err(err) => {
+                eprintln!("{} day {:02} part {}: {}", day.year, day.day, part, err);
+                failed += 1;
+            }
+        }
+    }
+    failed
+}
+
+/// Benchmarks `parts` of each day, printing a table or, if `json` is set, a JSON array with an
+/// object per day. Returns how many days failed.
+fn bench_days(
+    days: &[&Day],
+    parts: &[Part],
+    locator: &Locator,
+    options: &bench::Options,
+    json: bool,
+) -> Result<usize> {
+    let mut failed = 0;
+    let mut results = Vec::new();
+    for day in days {
+        let timings = match locator
+            .read(day.year, day.day)
+            .and_then(|input| day.bench(&input, parts, options))
+        {
+            Ok(timings) => timings,
+            Err(err) => {
+                eprintln!("{} day {:02}: {}", day.year, day.day, err);
+                failed += 1;
+                continue;
+            }
+        };
+
+        if json {
+            results.push(bench_json(day, options, &timings));
+        } else {
+            for (phase, stats) in timings {
+                println!(
+                    "{} day {:02} {:<5}  min {:>10}  median {:>10}  max {:>10}",
+                    day.year,
+                    day.day,
+                    phase.name(),
+                    format!("{:.2?}", stats.min),
+                    format!("{:.2?}", stats.median),
+                    format!("{:.2?}", stats.max),
+                );
+            }
+        }
+    }
+
+    if json {
+        println!("{}", serde_json::Value::Array(results));
+    }
+    Ok(failed)
+}
+
+/// A day's timings as printed by `--json`, in nanoseconds, keyed by phase.
+pub fn bench_json(
+    day: &Day,
+    options: &bench::Options,
+    timings: &[(Phase, Stats)],
+) -> serde_json::Value {
+    let nanos = |duration: std::time::Duration| duration.as_nanos() as u64;
+    let phases = timings
+        .iter()
+        .map(|(phase, stats)| {
+            (
+                phase.name().to_string(),
+                serde_json::json!({
+                    "min_ns": nanos(stats.min),
+                    "median_ns": nanos(stats.median),
+                    "max_ns": nanos(stats.max),
+                }),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+    serde_json::json!({
+        "year": day.year,
+        "day": day.day,
+        "warmup": options.warmup,
+        "iterations": options.iterations,
+        "phases": phases,
+    })
+}
@@ -0,0 +1,10 @@
+fn main() {
+    match aoc::run(std::env::args().skip(1)) {
+        Ok(0) => (),
+        Ok(_failed) => std::process::exit(1),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
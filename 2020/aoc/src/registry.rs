@@ -0,0 +1,69 @@
+use util::bench::{self, Phase, Stats};
+use util::{Answer, Part, Result, Solution};
+
+/// Parses a day's input once and solves each of the given parts.
+type AnswersFn = fn(&str, &[Part]) -> Result<Vec<Result<Answer>>>;
+
+/// Times parsing a day's input and solving each of the given parts.
+type BenchFn = fn(&str, &[Part], &bench::Options) -> Result<Vec<(Phase, Stats)>>;
+
+/// A registered puzzle, backed by the day's `Solution` implementation.
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    answers: AnswersFn,
+    bench: BenchFn,
+}
+
+impl Day {
+    /// Parses `input` once and solves each of `parts`, in order.
+    pub fn answers(&self, input: &str, parts: &[Part]) -> Result<Vec<Result<Answer>>> {
+        (self.answers)(input, parts)
+    }
+
+    /// Times parsing `input` and solving each of `parts`.
+    pub fn bench(
+        &self,
+        input: &str,
+        parts: &[Part],
+        options: &bench::Options,
+    ) -> Result<Vec<(Phase, Stats)>> {
+        (self.bench)(input, parts, options)
+    }
+}
+
+macro_rules! day {
+    ($year:expr, $day:expr, $solution:ty) => {
+        Day {
+            year: $year,
+            day: $day,
+            answers: <$solution as Solution>::answers,
+            bench: bench::bench::<$solution>,
+        }
+    };
+}
+
+pub static DAYS: &[Day] = &[
+    day!(2020, 1, _01::Day01),
+    day!(2020, 2, _02::Day02),
+    day!(2020, 3, _03::Day03),
+    day!(2020, 4, _04::Day04),
+    day!(2020, 5, _05::Day05),
+    day!(2020, 6, _06::Day06),
+    day!(2020, 7, _07::Day07),
+    day!(2020, 8, _08::Day08),
+    day!(2020, 9, _09::Day09),
+    day!(2020, 10, _10::Day10),
+    day!(2020, 11, _11::Day11),
+    day!(2020, 12, _12::Day12),
+];
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+/// Returns every registered day of `year` whose day number falls within `first..=last`.
+pub fn select(year: u16, first: u8, last: u8) -> impl Iterator<Item = &'static Day> {
+    DAYS.iter()
+        .filter(move |d| d.year == year && d.day >= first && d.day <= last)
+}
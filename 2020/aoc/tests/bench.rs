@@ -0,0 +1,41 @@
+use aoc::{bench_json, registry};
+use serde_json::json;
+use std::time::Duration;
+use util::bench::{Options, Phase, Stats};
+use util::Part;
+
+fn stats(min: u64, median: u64, max: u64) -> Stats {
+    Stats {
+        min: Duration::from_nanos(min),
+        median: Duration::from_nanos(median),
+        max: Duration::from_nanos(max),
+    }
+}
+
+#[test]
+fn reports_each_phase_in_nanoseconds() {
+    let day = registry::find(2020, 7).unwrap();
+    let options = Options {
+        warmup: 2,
+        iterations: 5,
+    };
+    let timings = [
+        (Phase::Parse, stats(100, 150, 300)),
+        (Phase::Solve(Part::One), stats(1_000, 1_500, 2_000)),
+        (Phase::Solve(Part::Two), stats(7, 8, 9)),
+    ];
+    assert_eq!(
+        bench_json(day, &options, &timings),
+        json!({
+            "year": 2020,
+            "day": 7,
+            "warmup": 2,
+            "iterations": 5,
+            "phases": {
+                "parse": { "min_ns": 100, "median_ns": 150, "max_ns": 300 },
+                "part1": { "min_ns": 1000, "median_ns": 1500, "max_ns": 2000 },
+                "part2": { "min_ns": 7, "median_ns": 8, "max_ns": 9 },
+            },
+        })
+    );
+}
@@ -0,0 +1,141 @@
+use aoc::cli::{self, Command};
+use util::{bench, Error, Part, Result};
+
+fn parse(args: &str) -> Result<Command> {
+    cli::parse(args.split_whitespace().map(String::from))
+}
+
+/// The days selected by a `run` command.
+fn days(args: &str) -> Result<(u8, u8)> {
+    match parse(args)? {
+        Command::Run { first, last, .. } => Ok((first, last)),
+        command => panic!("expected a run command, got {:?}", command),
+    }
+}
+
+fn usage_error(args: &str) -> String {
+    match parse(args) {
+        Err(Error::Usage(message)) => message,
+        Err(err) => panic!("expected a usage error for `{}`, got {}", args, err),
+        Ok(command) => panic!("expected a usage error for `{}`, got {:?}", args, command),
+    }
+}
+
+#[test]
+fn parses_a_run_with_its_defaults() {
+    assert_eq!(
+        parse("run 2020 7").unwrap(),
+        Command::Run {
+            year: 2020,
+            first: 7,
+            last: 7,
+            parts: vec![Part::One, Part::Two],
+            input_dir: None,
+            input: None,
+            bench: None,
+            json: false,
+        }
+    );
+}
+
+#[test]
+fn parses_run_options() {
+    assert_eq!(
+        parse("run 2020 3 --part 2 --input-dir inputs --input -").unwrap(),
+        Command::Run {
+            year: 2020,
+            first: 3,
+            last: 3,
+            parts: vec![Part::Two],
+            input_dir: Some("inputs".into()),
+            input: Some("-".into()),
+            bench: None,
+            json: false,
+        }
+    );
+}
+
+#[test]
+fn selects_a_day_a_range_or_every_day() {
+    assert_eq!(days("run 2020").unwrap(), (1, 25));
+    assert_eq!(days("run 2020 all").unwrap(), (1, 25));
+    assert_eq!(days("run 2020 12").unwrap(), (12, 12));
+    assert_eq!(days("run 2020 3-9").unwrap(), (3, 9));
+    assert_eq!(days("run 2020 1-25").unwrap(), (1, 25));
+    assert_eq!(days("run 2020 4-4").unwrap(), (4, 4));
+}
+
+#[test]
+fn rejects_days_outside_of_advent() {
+    assert_eq!(
+        usage_error("run 2020 0"),
+        "`0` is not a day between 1 and 25"
+    );
+    assert_eq!(
+        usage_error("run 2020 26"),
+        "`26` is not a day between 1 and 25"
+    );
+    assert_eq!(
+        usage_error("run 2020 0-3"),
+        "`0` is not a day between 1 and 25"
+    );
+    assert_eq!(
+        usage_error("run 2020 3-x"),
+        "`x` is not a day between 1 and 25"
+    );
+    assert_eq!(usage_error("run 2020 9-3"), "`9-3` is an empty range");
+}
+
+#[test]
+fn rejects_extra_and_unknown_arguments() {
+    assert_eq!(usage_error("run 2020 7 8"), "unexpected argument `8`");
+    assert_eq!(
+        usage_error("run 2020 --verbose"),
+        "unknown flag `--verbose`"
+    );
+    assert_eq!(usage_error("run twenty"), "year must be a number");
+    assert_eq!(
+        usage_error("run 2020 --part 3"),
+        "`--part` expects either 1 or 2"
+    );
+    assert!(usage_error("build 2020").starts_with("unknown command `build`"));
+    assert_eq!(usage_error(""), cli::USAGE);
+    assert_eq!(usage_error("run"), cli::USAGE);
+}
+
+#[test]
+fn only_reads_an_explicit_input_for_a_single_day() {
+    assert_eq!(
+        usage_error("run 2020 1-3 --input day.txt"),
+        "`--input` can only be used when running a single day"
+    );
+}
+
+#[test]
+fn checks_benchmark_options() {
+    match parse("run 2020 --bench --warmup 0 --iterations 5 --json").unwrap() {
+        Command::Run { bench, json, .. } => {
+            assert_eq!(
+                bench,
+                Some(bench::Options {
+                    warmup: 0,
+                    iterations: 5
+                })
+            );
+            assert!(json);
+        }
+        command => panic!("expected a run command, got {:?}", command),
+    }
+    assert_eq!(
+        usage_error("run 2020 --bench --iterations 0"),
+        "`--iterations` must be at least 1"
+    );
+    assert_eq!(
+        usage_error("run 2020 --json"),
+        "`--json` can only be used with `--bench`"
+    );
+    assert_eq!(
+        usage_error("run 2020 --bench --warmup many"),
+        "`--warmup` expects a number"
+    );
+}
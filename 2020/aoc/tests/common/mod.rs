@@ -0,0 +1,173 @@
+//! A stand-in for the puzzle server, a clock the tests control, and scratch directories for the
+//! tests to cache inputs in.
+
+#![allow(dead_code)]
+
+use aoc::client::Clock;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+pub use util::testing::TempDir;
+
+/// A clock that only moves when something sleeps on it, recording each sleep.
+#[derive(Clone)]
+pub struct FakeClock {
+    now: Rc<Cell<SystemTime>>,
+    slept: Rc<RefCell<Vec<Duration>>>,
+}
+
+impl FakeClock {
+    pub fn starting_at(now: SystemTime) -> Self {
+        FakeClock {
+            now: Rc::new(Cell::new(now)),
+            slept: Rc::default(),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+
+    pub fn slept(&self) -> Vec<Duration> {
+        self.slept.borrow().clone()
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> SystemTime {
+        self.now.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.slept.borrow_mut().push(duration);
+        self.advance(duration);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn ok(body: impl Into<String>) -> Self {
+        Response {
+            status: 200,
+            body: body.into(),
+        }
+    }
+
+    pub fn status(status: u16) -> Self {
+        Response {
+            status,
+            body: String::new(),
+        }
+    }
+}
+
+/// A minimal HTTP server answering each request with `handler` and recording every request it
+/// receives.
+pub struct Server {
+    url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl Server {
+    pub fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&Request) -> Response + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind the stand-in server");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+                if let Some(request) = read_request(&stream) {
+                    let response = handler(&request);
+                    recorded.lock().unwrap().push(request);
+                    let _ = write!(
+                        stream,
+                        "HTTP/1.1 {} Canned\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        response.status,
+                        response.body.len(),
+                        response.body
+                    );
+                }
+            }
+        });
+        Server { url, requests }
+    }
+
+    /// A server that serves `inputs` keyed by `/<year>/day/<day>/input` path, and 404s otherwise.
+    pub fn with_inputs(inputs: &[(&str, &str)]) -> Self {
+        let inputs = inputs
+            .iter()
+            .map(|(path, input)| (path.to_string(), input.to_string()))
+            .collect::<HashMap<_, _>>();
+        Server::start(move |request| match inputs.get(&request.path) {
+            Some(input) => Response::ok(input.clone()),
+            None => Response::status(404),
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn read_request(stream: &TcpStream) -> Option<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.to_string();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(':')?;
+        headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+    }
+
+    let length = headers
+        .get("content-length")
+        .and_then(|length| length.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+    Some(Request {
+        method,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
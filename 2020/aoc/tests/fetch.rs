@@ -0,0 +1,187 @@
+mod common;
+
+use aoc::client::{Client, RateLimiter};
+use common::{FakeClock, Server, TempDir};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use util::input::Locator;
+
+/// When the fake clock starts, in milliseconds since the Unix epoch.
+const START_MILLIS: u64 = 1_606_798_800_000;
+
+fn start() -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(START_MILLIS)
+}
+
+fn client(server: &Server, dir: &TempDir, min_interval: Duration) -> Client {
+    let rate_limiter = RateLimiter::new(min_interval, dir.path().join(".last-request"));
+    Client::new(server.url(), "abc123", rate_limiter)
+}
+
+fn client_with_clock(
+    server: &Server,
+    dir: &TempDir,
+    min_interval: Duration,
+    clock: &FakeClock,
+) -> Client {
+    let rate_limiter =
+        RateLimiter::new(min_interval, dir.path().join(".last-request")).with_clock(clock.clone());
+    Client::new(server.url(), "abc123", rate_limiter)
+}
+
+#[test]
+fn downloads_inputs_into_the_cache() {
+    let server = Server::with_inputs(&[("/2020/day/1/input", "1721\n979\n")]);
+    let dir = TempDir::new();
+    let locator = Locator::default().root(dir.path());
+
+    let failed = aoc::fetch(
+        &client(&server, &dir, Duration::ZERO),
+        &locator,
+        2020,
+        1..=1,
+    );
+
+    assert_eq!(failed, 0);
+    let cached = dir.path().join("2020").join("_01").join("input.txt");
+    assert_eq!(std::fs::read_to_string(&cached).unwrap(), "1721\n979\n");
+    assert_eq!(locator.read(2020, 1).unwrap(), "1721\n979\n");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].headers["cookie"], "session=abc123");
+    assert!(requests[0].headers["user-agent"].contains("bgourlie"));
+}
+
+#[test]
+fn never_refetches_a_cached_input() {
+    let server = Server::with_inputs(&[("/2020/day/2/input", "fresh\n")]);
+    let dir = TempDir::new();
+    let locator = Locator::default().root(dir.path());
+    let cached = locator.cache_path(2020, 2);
+    std::fs::create_dir_all(cached.parent().unwrap()).unwrap();
+    std::fs::write(&cached, "cached\n").unwrap();
+
+    let client = client(&server, &dir, Duration::ZERO);
+    assert_eq!(aoc::fetch(&client, &locator, 2020, 2..=2), 0);
+    assert_eq!(aoc::fetch(&client, &locator, 2020, 2..=2), 0);
+
+    assert!(server.requests().is_empty());
+    assert_eq!(std::fs::read_to_string(&cached).unwrap(), "cached\n");
+}
+
+/// The rate limiter's stamp, in milliseconds since the Unix epoch.
+fn stamp(dir: &TempDir) -> u64 {
+    std::fs::read_to_string(dir.path().join(".last-request"))
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn spaces_out_requests() {
+    let server = Server::with_inputs(&[
+        ("/2020/day/1/input", "1\n"),
+        ("/2020/day/2/input", "2\n"),
+        ("/2020/day/3/input", "3\n"),
+    ]);
+    let dir = TempDir::new();
+    let locator = Locator::default().root(dir.path());
+    let clock = FakeClock::starting_at(start());
+    let min_interval = Duration::from_secs(5);
+
+    let client = client_with_clock(&server, &dir, min_interval, &clock);
+    let failed = aoc::fetch(&client, &locator, 2020, 1..=3);
+
+    assert_eq!(failed, 0);
+    let paths = server
+        .requests()
+        .into_iter()
+        .map(|request| request.path)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        vec![
+            "/2020/day/1/input",
+            "/2020/day/2/input",
+            "/2020/day/3/input"
+        ]
+    );
+    // The first request goes straight away, and each after it waits out the whole interval
+    assert_eq!(clock.slept(), vec![min_interval, min_interval]);
+    assert_eq!(stamp(&dir), START_MILLIS + 10_000);
+}
+
+#[test]
+fn rate_limit_holds_across_clients() {
+    let server = Server::with_inputs(&[("/2020/day/1/input", "1\n"), ("/2020/day/2/input", "2\n")]);
+    let dir = TempDir::new();
+    let locator = Locator::default().root(dir.path());
+    let clock = FakeClock::starting_at(start());
+    let min_interval = Duration::from_secs(5);
+
+    let first = client_with_clock(&server, &dir, min_interval, &clock);
+    aoc::fetch(&first, &locator, 2020, 1..=1);
+    assert_eq!(stamp(&dir), START_MILLIS);
+    clock.advance(Duration::from_secs(2));
+    let second = client_with_clock(&server, &dir, min_interval, &clock);
+    aoc::fetch(&second, &locator, 2020, 2..=2);
+
+    assert_eq!(server.requests().len(), 2);
+    // The second client only waits for what's left of the interval since the first's request
+    assert_eq!(clock.slept(), vec![Duration::from_secs(3)]);
+    assert_eq!(stamp(&dir), START_MILLIS + 5_000);
+}
+
+#[test]
+fn doesnt_wait_once_the_interval_has_passed() {
+    let server = Server::with_inputs(&[("/2020/day/1/input", "1\n"), ("/2020/day/2/input", "2\n")]);
+    let dir = TempDir::new();
+    let locator = Locator::default().root(dir.path());
+    let clock = FakeClock::starting_at(start());
+
+    let client = client_with_clock(&server, &dir, Duration::from_secs(5), &clock);
+    aoc::fetch(&client, &locator, 2020, 1..=1);
+    clock.advance(Duration::from_secs(6));
+    aoc::fetch(&client, &locator, 2020, 2..=2);
+
+    assert_eq!(server.requests().len(), 2);
+    assert!(clock.slept().is_empty());
+    assert_eq!(stamp(&dir), START_MILLIS + 6_000);
+}
+
+#[test]
+fn failed_downloads_are_not_cached() {
+    let server = Server::with_inputs(&[("/2020/day/1/input", "1\n")]);
+    let dir = TempDir::new();
+    let locator = Locator::default().root(dir.path());
+
+    let failed = aoc::fetch(
+        &client(&server, &dir, Duration::ZERO),
+        &locator,
+        2020,
+        1..=2,
+    );
+
+    assert_eq!(failed, 1);
+    assert!(locator.cache_path(2020, 1).exists());
+    assert!(!locator.cache_path(2020, 2).exists());
+    assert!(!locator
+        .cache_path(2020, 2)
+        .parent()
+        .unwrap()
+        .join("input.txt.partial")
+        .exists());
+}
+
+#[test]
+fn reports_why_a_download_failed() {
+    let server = Server::start(|_| common::Response::status(400));
+    let dir = TempDir::new();
+
+    let err = client(&server, &dir, Duration::ZERO)
+        .fetch_input(2020, 1)
+        .unwrap_err();
+
+    assert!(err.to_string().contains("session token"), "{}", err);
+}
@@ -0,0 +1,162 @@
+mod common;
+
+use aoc::client::{Client, RateLimiter, Verdict};
+use aoc::ledger::Ledger;
+use common::{Response, Server, TempDir};
+use std::time::Duration;
+use util::{Answer, Part};
+
+const CORRECT: &str = "<article><p>That's the right answer!  You are one gold star closer to \
+                       saving your vacation.</p></article>";
+const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If \
+                        you're stuck, make sure you're using the full input data.  Please wait \
+                        one minute before trying again.</p></article>";
+const TOO_LOW: &str =
+    "<article><p>That's not the right answer; your answer is too low.</p></article>";
+const TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to wait after \
+                          submitting an answer before trying again.  You have 1m 20s left to \
+                          wait.</p></article>";
+
+fn client(server: &Server, dir: &TempDir) -> Client {
+    let rate_limiter = RateLimiter::new(Duration::ZERO, dir.path().join(".last-request"));
+    Client::new(server.url(), "abc123", rate_limiter)
+}
+
+fn ledger(dir: &TempDir) -> Ledger {
+    Ledger::load(Ledger::path(dir.path(), 2020)).unwrap()
+}
+
+#[test]
+fn parses_verdicts() {
+    assert_eq!(Verdict::parse(CORRECT), Some(Verdict::Correct));
+    assert_eq!(Verdict::parse(TOO_HIGH), Some(Verdict::TooHigh));
+    assert_eq!(Verdict::parse(TOO_LOW), Some(Verdict::TooLow));
+    assert_eq!(
+        Verdict::parse(TOO_RECENT),
+        Some(Verdict::Wait(Duration::from_secs(80)))
+    );
+    assert_eq!(
+        Verdict::parse(
+            "You don't seem to be solving the right level.  Did you already complete it?"
+        ),
+        Some(Verdict::WrongLevel)
+    );
+    assert_eq!(Verdict::parse("<html>Something else</html>"), None);
+}
+
+#[test]
+fn records_correct_answers() {
+    let server = Server::start(|_| Response::ok(CORRECT));
+    let dir = TempDir::new();
+    let mut ledger = ledger(&dir);
+
+    let verdict = aoc::submit(
+        &client(&server, &dir),
+        &mut ledger,
+        2020,
+        7,
+        Part::Two,
+        &Answer::from(10875),
+    )
+    .unwrap();
+
+    assert_eq!(verdict, Verdict::Correct);
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2020/day/7/answer");
+    assert_eq!(requests[0].headers["cookie"], "session=abc123");
+    assert_eq!(requests[0].body, "level=2&answer=10875");
+    assert_eq!(self::ledger(&dir).get(7, Part::Two), Some("10875"));
+    assert_eq!(self::ledger(&dir).get(7, Part::One), None);
+}
+
+#[test]
+fn does_not_record_wrong_answers() {
+    let server = Server::start(|_| Response::ok(TOO_LOW));
+    let dir = TempDir::new();
+    let mut ledger = ledger(&dir);
+
+    let verdict = aoc::submit(
+        &client(&server, &dir),
+        &mut ledger,
+        2020,
+        1,
+        Part::One,
+        &Answer::from(5),
+    )
+    .unwrap();
+
+    assert_eq!(verdict, Verdict::TooLow);
+    assert_eq!(ledger.get(1, Part::One), None);
+    assert!(!Ledger::path(dir.path(), 2020).exists());
+}
+
+#[test]
+fn does_not_resubmit_verified_answers() {
+    let server = Server::start(|_| Response::ok(CORRECT));
+    let dir = TempDir::new();
+    let mut ledger = ledger(&dir);
+    ledger.record(1, Part::One, &Answer::from(514579));
+    let client = client(&server, &dir);
+
+    let verdict = aoc::submit(
+        &client,
+        &mut ledger,
+        2020,
+        1,
+        Part::One,
+        &Answer::from(514579),
+    );
+    assert_eq!(verdict.unwrap(), Verdict::Correct);
+    let err = aoc::submit(&client, &mut ledger, 2020, 1, Part::One, &Answer::from(1)).unwrap_err();
+    assert!(err.to_string().contains("514579"), "{}", err);
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn unrecognised_responses_are_errors() {
+    let server = Server::start(|_| Response::ok("<html>Maintenance</html>"));
+    let dir = TempDir::new();
+
+    let result = aoc::submit(
+        &client(&server, &dir),
+        &mut ledger(&dir),
+        2020,
+        1,
+        Part::One,
+        &Answer::from(1),
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn runs_flag_regressions() {
+    let dir = TempDir::new();
+    let input = dir.path().join("2020").join("_01").join("input.txt");
+    std::fs::create_dir_all(input.parent().unwrap()).unwrap();
+    std::fs::write(&input, "1721\n979\n366\n299\n675\n1456\n").unwrap();
+    let run = || {
+        let args = [
+            "run",
+            "2020",
+            "1",
+            "--input-dir",
+            dir.path().to_str().unwrap(),
+        ];
+        aoc::run(args.iter().map(|arg| arg.to_string())).unwrap()
+    };
+
+    assert_eq!(run(), 0);
+
+    let mut ledger = ledger(&dir);
+    ledger.record(1, Part::One, &Answer::from(514579));
+    ledger.record(1, Part::Two, &Answer::from(241861950));
+    ledger.save().unwrap();
+    assert_eq!(run(), 0);
+
+    ledger.record(1, Part::Two, &Answer::from(12345));
+    ledger.save().unwrap();
+    assert_eq!(run(), 1);
+}
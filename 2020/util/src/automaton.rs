@@ -0,0 +1,441 @@
+//! Cellular automata on a [`Grid`], where every cell's next state depends only on its own state and
+//! how many of its neighbours are live.
+//!
+//! A simulation is described by a [`Rule`] and a [`Neighbourhood`]. For example, Conway's Game of
+//! Life is:
+//!
+//! ```
+//! use util::automaton::{Automaton, Neighbourhood, Outcome, Rule};
+//! use util::grid::Grid;
+//!
+//! struct Life;
+//!
+//! impl Rule for Life {
+//!     type Cell = bool;
+//!
+//!     fn is_live(&self, cell: &bool) -> bool {
+//!         *cell
+//!     }
+//!
+//!     fn next(&self, cell: &bool, live_neighbours: usize) -> bool {
+//!         matches!((cell, live_neighbours), (true, 2) | (_, 3))
+//!     }
+//! }
+//!
+//! let blinker = Grid::parse(".....\n..#..\n..#..\n..#..\n.....", |chr| Ok::<_, &str>(chr == '#'))?;
+//! let mut life = Automaton::new(Neighbourhood::moore(&blinker), blinker, Life);
+//! assert_eq!(life.run(100), Outcome::Cycle { start: 0, period: 2 });
+//! # Ok::<(), util::Error>(())
+//! ```
+
+use crate::grid::{self, Grid};
+
+/// How a cell changes from one generation to the next.
+pub trait Rule {
+    type Cell: Clone + Eq;
+
+    /// Whether a cell counts towards the live neighbours of the cells around it.
+    fn is_live(&self, cell: &Self::Cell) -> bool;
+
+    /// The state a cell will have in the next generation.
+    fn next(&self, cell: &Self::Cell, live_neighbours: usize) -> Self::Cell;
+
+    /// Whether a cell can never change, like a wall or floor. Fixed cells are skipped when working
+    /// out the next generation.
+    fn is_fixed(&self, _cell: &Self::Cell) -> bool {
+        false
+    }
+}
+
+/// The cells that each cell of a grid counts as its neighbours, by index into the grid's cells.
+pub struct Neighbourhood {
+    /// The neighbours of cell `i` are `neighbours[starts[i]..starts[i + 1]]`.
+    starts: Vec<usize>,
+    neighbours: Vec<usize>,
+}
+
+impl Neighbourhood {
+    /// The cells at each of `offsets` from a cell.
+    pub fn offsets<T>(grid: &Grid<T>, offsets: &[(isize, isize)]) -> Self {
+        Neighbourhood::from_table(&grid.visibility_table(offsets, Some(1), |_| true))
+    }
+
+    /// The eight surrounding cells.
+    pub fn moore<T>(grid: &Grid<T>) -> Self {
+        Neighbourhood::offsets(grid, &grid::ALL_DIRECTIONS)
+    }
+
+    /// The four orthogonally adjacent cells.
+    pub fn von_neumann<T>(grid: &Grid<T>) -> Self {
+        Neighbourhood::offsets(grid, &grid::ORTHOGONAL)
+    }
+
+    /// The neighbours listed for each cell by a [`Grid::visibility_table`], flattened into one
+    /// allocation.
+    pub fn from_table(table: &Grid<Vec<usize>>) -> Self {
+        let mut starts = Vec::with_capacity(table.cells().len() + 1);
+        let mut neighbours = Vec::new();
+        starts.push(0);
+        for visible in table.cells() {
+            neighbours.extend_from_slice(visible);
+            starts.push(neighbours.len());
+        }
+        Neighbourhood { starts, neighbours }
+    }
+
+    /// The neighbours of the cell at `index`.
+    pub fn of(&self, index: usize) -> &[usize] {
+        &self.neighbours[self.starts[index]..self.starts[index + 1]]
+    }
+
+    /// The reverse neighbourhood, of the cells that count each cell as a neighbour, ignoring the
+    /// neighbours of cells for which `include` is false.
+    fn dependents<F: Fn(usize) -> bool>(&self, include: F) -> Neighbourhood {
+        let len = self.starts.len() - 1;
+        let mut starts = vec![0; len + 1];
+        for index in (0..len).filter(|&index| include(index)) {
+            for &neighbour in self.of(index) {
+                starts[neighbour + 1] += 1;
+            }
+        }
+        for index in 0..len {
+            starts[index + 1] += starts[index];
+        }
+        let mut next = starts.clone();
+        let mut dependents = vec![0; starts[len]];
+        for index in (0..len).filter(|&index| include(index)) {
+            for &neighbour in self.of(index) {
+                dependents[next[neighbour]] = index;
+                next[neighbour] += 1;
+            }
+        }
+        Neighbourhood {
+            starts,
+            neighbours: dependents,
+        }
+    }
+}
+
+/// How an [`Automaton`] decides which cells to update each generation.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Stepping {
+    /// Every cell is updated every generation.
+    #[default]
+    Full,
+    /// Only cells that changed in the previous generation, or have a neighbour that did, are
+    /// updated. Gives the same results as `Full`, but is much faster once most of the grid has
+    /// settled.
+    Incremental,
+}
+
+/// How a simulation run by [`Automaton::run`] ended.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    /// A fixed point was reached after `generation` generations.
+    Stable { generation: usize },
+    /// The state at generation `start` recurs every `period` generations.
+    Cycle { start: usize, period: usize },
+    /// Neither a fixed point nor a cycle was found within the generation limit.
+    Limit,
+}
+
+/// Steps an automaton a generation using the given number of threads.
+type ParallelStep<R> = fn(&mut Automaton<R>, usize) -> usize;
+
+pub struct Automaton<R: Rule> {
+    neighbourhood: Neighbourhood,
+    current: Grid<R::Cell>,
+    /// The buffer the next generation is written to before being swapped with `current`.
+    next: Grid<R::Cell>,
+    rule: R,
+    generation: usize,
+    stepping: Stepping,
+    /// Bookkeeping for incremental stepping, kept up to date only while it's in use.
+    incremental: Option<Incremental>,
+    /// The number of threads and the function that steps with them, when stepping in parallel.
+    /// Only [`Automaton::with_parallel_stepping`], which requires the rule and cells to be shareable
+    /// between threads, can set this, so the rest of the automaton doesn't need them to be.
+    parallel: Option<(usize, ParallelStep<R>)>,
+}
+
+struct Incremental {
+    /// The cells whose neighbourhood includes each cell.
+    dependents: Neighbourhood,
+    /// How many of each cell's neighbours are live.
+    live_neighbours: Vec<usize>,
+    /// Whether each cell might change in the next generation.
+    dirty: Vec<bool>,
+    /// The buffer the dirty cells for the generation after next are marked in.
+    next_dirty: Vec<bool>,
+}
+
+impl<R: Rule> Automaton<R> {
+    pub fn new(neighbourhood: Neighbourhood, cells: Grid<R::Cell>, rule: R) -> Self {
+        Automaton {
+            neighbourhood,
+            next: cells.clone(),
+            current: cells,
+            rule,
+            generation: 0,
+            stepping: Stepping::default(),
+            incremental: None,
+            parallel: None,
+        }
+    }
+
+    pub fn with_stepping(mut self, stepping: Stepping) -> Self {
+        self.incremental = match stepping {
+            Stepping::Incremental => Some(self.incremental_state()),
+            Stepping::Full => None,
+        };
+        self.stepping = stepping;
+        self.parallel = None;
+        self
+    }
+
+    fn incremental_state(&self) -> Incremental {
+        let (rule, cells) = (&self.rule, self.current.cells());
+        let live_neighbours = (0..cells.len())
+            .map(|index| live_neighbours(rule, &self.neighbourhood, cells, index))
+            .collect();
+        Incremental {
+            dependents: self
+                .neighbourhood
+                .dependents(|index| !rule.is_fixed(&cells[index])),
+            live_neighbours,
+            // Nothing is known yet about which cells will change first
+            dirty: cells.iter().map(|cell| !rule.is_fixed(cell)).collect(),
+            next_dirty: vec![false; cells.len()],
+        }
+    }
+
+    pub fn cells(&self) -> &Grid<R::Cell> {
+        &self.current
+    }
+
+    /// The number of generations that have changed the grid so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn into_cells(self) -> Grid<R::Cell> {
+        self.current
+    }
+
+    /// Advances the simulation by a generation, returning how many cells changed. A step that
+    /// changes nothing has reached a fixed point, and doesn't count as a generation.
+    pub fn step(&mut self) -> usize {
+        if let Some((threads, step_parallel)) = self.parallel {
+            return step_parallel(self, threads);
+        }
+        match self.stepping {
+            Stepping::Full => self.step_full(),
+            Stepping::Incremental => self.step_incremental(),
+        }
+    }
+
+    fn step_full(&mut self) -> usize {
+        let changed = next_generation(
+            &self.rule,
+            &self.neighbourhood,
+            self.current.cells(),
+            0,
+            self.next.cells_mut(),
+        );
+        if changed > 0 {
+            std::mem::swap(&mut self.current, &mut self.next);
+            self.generation += 1;
+        }
+        changed
+    }
+
+    fn step_incremental(&mut self) -> usize {
+        let rule = &self.rule;
+        let state = self
+            .incremental
+            .as_mut()
+            .expect("incremental state is set up by with_stepping");
+        let cells = self.current.cells_mut();
+
+        // Dirty cells are visited in order rather than from a list of indices, which keeps memory
+        // access sequential while most of the grid is still changing. Every change is decided
+        // before any is applied, so they all see the same generation.
+        let changes = cells
+            .iter()
+            .zip(&state.dirty)
+            .zip(&state.live_neighbours)
+            .enumerate()
+            .filter(|(_, ((_, &dirty), _))| dirty)
+            .filter_map(|(index, ((cell, _), &live_neighbours))| {
+                let next_cell = rule.next(cell, live_neighbours);
+                (next_cell != *cell).then_some((index, next_cell))
+            })
+            .collect::<Vec<_>>();
+        if changes.is_empty() {
+            return 0;
+        }
+
+        for (index, next_cell) in changes.iter().cloned() {
+            let (was_live, is_live) = (rule.is_live(&cells[index]), rule.is_live(&next_cell));
+            cells[index] = next_cell;
+            state.next_dirty[index] = true;
+            if was_live != is_live {
+                for &dependent in state.dependents.of(index) {
+                    if is_live {
+                        state.live_neighbours[dependent] += 1;
+                    } else {
+                        state.live_neighbours[dependent] -= 1;
+                    }
+                    state.next_dirty[dependent] = true;
+                }
+            }
+        }
+        std::mem::swap(&mut state.dirty, &mut state.next_dirty);
+        state.next_dirty.iter_mut().for_each(|dirty| *dirty = false);
+        self.generation += 1;
+        changes.len()
+    }
+
+    /// Steps until the grid reaches a fixed point or repeats an earlier state, running at most
+    /// `max_generations` generations.
+    pub fn run(&mut self, max_generations: usize) -> Outcome {
+        // Brent's algorithm: compare each generation against a saved one, saving a new one each
+        // time the distance from the last reaches the next power of two. This finds any cycle
+        // without keeping every generation around.
+        let (initial, initial_generation) = (self.current.clone(), self.generation);
+        let mut saved = self.current.clone();
+        let (mut power, mut distance) = (1, 0);
+        for _ in 0..max_generations {
+            if self.step() == 0 {
+                return Outcome::Stable {
+                    generation: self.generation,
+                };
+            }
+            distance += 1;
+            if self.current == saved {
+                return Outcome::Cycle {
+                    start: initial_generation + self.cycle_start(initial, distance),
+                    period: distance,
+                };
+            }
+            if distance == power {
+                saved.clone_from(&self.current);
+                power *= 2;
+                distance = 0;
+            }
+        }
+        Outcome::Limit
+    }
+
+    /// Finds how many generations after `initial` a cycle of `period` generations begins, by
+    /// replaying the simulation from `initial` with a second copy `period` generations ahead.
+    fn cycle_start(&self, initial: Grid<R::Cell>, period: usize) -> usize {
+        let mut scratch = initial.clone();
+        let mut advance = |grid: &mut Grid<R::Cell>| {
+            next_generation(
+                &self.rule,
+                &self.neighbourhood,
+                grid.cells(),
+                0,
+                scratch.cells_mut(),
+            );
+            std::mem::swap(grid, &mut scratch);
+        };
+
+        let (mut behind, mut ahead) = (initial.clone(), initial);
+        for _ in 0..period {
+            advance(&mut ahead);
+        }
+        let mut start = 0;
+        while behind != ahead {
+            advance(&mut behind);
+            advance(&mut ahead);
+            start += 1;
+        }
+        start
+    }
+}
+
+impl<R> Automaton<R>
+where
+    R: Rule + Sync,
+    R::Cell: Send + Sync,
+{
+    /// Updates every cell every generation, with the rows of the grid split between `threads`
+    /// threads. Gives the same results as [`Stepping::Full`], but is faster for large grids.
+    pub fn with_parallel_stepping(self, threads: usize) -> Self {
+        let mut automaton = self.with_stepping(Stepping::Full);
+        automaton.parallel = Some((threads.max(1), Self::step_parallel));
+        automaton
+    }
+
+    fn step_parallel(&mut self, threads: usize) -> usize {
+        let (rule, neighbourhood) = (&self.rule, &self.neighbourhood);
+        let current = self.current.cells();
+        let width = self.current.width();
+        let rows_per_thread = self.current.height().div_ceil(threads).max(1);
+        let next = self.next.cells_mut();
+        let changed = std::thread::scope(|scope| {
+            // Each thread writes its own rows of the next generation, reading from the current one
+            let workers = next
+                .chunks_mut(rows_per_thread * width)
+                .enumerate()
+                .map(|(i, next)| {
+                    let first = i * rows_per_thread * width;
+                    scope.spawn(move || next_generation(rule, neighbourhood, current, first, next))
+                })
+                .collect::<Vec<_>>();
+            workers
+                .into_iter()
+                .map(|worker| {
+                    worker
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .sum::<usize>()
+        });
+        if changed > 0 {
+            std::mem::swap(&mut self.current, &mut self.next);
+            self.generation += 1;
+        }
+        changed
+    }
+}
+
+/// Writes the cells of the generation after `current`, starting from the cell at index `first`,
+/// to `next`, returning how many of them changed.
+fn next_generation<R: Rule>(
+    rule: &R,
+    neighbourhood: &Neighbourhood,
+    current: &[R::Cell],
+    first: usize,
+    next: &mut [R::Cell],
+) -> usize {
+    let mut changed = 0;
+    for (index, next) in (first..).zip(next) {
+        let cell = &current[index];
+        if rule.is_fixed(cell) {
+            *next = cell.clone();
+            continue;
+        }
+        let next_cell = rule.next(cell, live_neighbours(rule, neighbourhood, current, index));
+        if next_cell != *cell {
+            changed += 1;
+        }
+        *next = next_cell;
+    }
+    changed
+}
+
+fn live_neighbours<R: Rule>(
+    rule: &R,
+    neighbourhood: &Neighbourhood,
+    cells: &[R::Cell],
+    index: usize,
+) -> usize {
+    neighbourhood
+        .of(index)
+        .iter()
+        .filter(|&&neighbour| rule.is_live(&cells[neighbour]))
+        .count()
+}
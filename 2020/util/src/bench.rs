@@ -0,0 +1,95 @@
+//! Times each phase of a solution (parsing and each part) over repeated runs.
+
+use crate::{Part, Result, Solution};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Options {
+    /// Untimed runs of each phase before measuring.
+    pub warmup: usize,
+    /// Timed runs of each phase.
+    pub iterations: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            warmup: 3,
+            iterations: 10,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl Phase {
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Solve(Part::One) => "part1",
+            Phase::Solve(Part::Two) => "part2",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarises a non-empty set of timings. The median of an even number of samples is the mean
+    /// of the middle two.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+        Stats {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Runs `f` `options.warmup` times untimed, then `options.iterations` times timed.
+fn measure<T>(options: &Options, mut f: impl FnMut() -> Result<T>) -> Result<Stats> {
+    for _ in 0..options.warmup {
+        black_box(f()?);
+    }
+    let mut samples = Vec::with_capacity(options.iterations);
+    for _ in 0..options.iterations.max(1) {
+        let start = Instant::now();
+        let result = f()?;
+        samples.push(start.elapsed());
+        black_box(result);
+    }
+    Ok(Stats::from_samples(samples))
+}
+
+/// Times parsing `input` and then solving each of `parts`. Parsing is timed separately, so the
+/// timings for each part exclude it.
+pub fn bench<S: Solution>(
+    input: &str,
+    parts: &[Part],
+    options: &Options,
+) -> Result<Vec<(Phase, Stats)>> {
+    let mut timings = vec![(Phase::Parse, measure(options, || S::parse(input))?)];
+    let parsed = S::parse(input)?;
+    for part in parts.iter().copied() {
+        let stats = measure(options, || S::solve(&parsed, part))?;
+        timings.push((Phase::Solve(part), stats));
+    }
+    Ok(timings)
+}
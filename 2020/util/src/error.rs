@@ -0,0 +1,190 @@
+use crate::input::NotFound;
+use nom::InputLength;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+pub enum Error {
+    Io(io::Error),
+    /// The puzzle input could not be parsed. `line` and `column` are 1-based, and `snippet` is the
+    /// full text of the offending line.
+    Parse {
+        line: usize,
+        column: usize,
+        snippet: String,
+        message: String,
+    },
+    /// The input parsed, but has no answer.
+    NoSolution(String),
+    /// The input parsed, but violates one of the puzzle's constraints.
+    InvalidInput(String),
+    InputNotFound(NotFound),
+    /// The command line arguments were invalid.
+    Usage(String),
+    /// A request to the puzzle server failed.
+    Http(String),
+}
+
+impl Error {
+    /// Creates a parse error located at byte `offset` into `source`. An offset inside a character,
+    /// as a parser working on bytes can stop at, points at that character.
+    pub fn parse(source: &str, offset: usize, message: impl Into<String>) -> Self {
+        let mut offset = offset.min(source.len());
+        while !source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[offset..]
+            .find('\n')
+            .map_or(source.len(), |i| offset + i);
+        Error::Parse {
+            line: source[..offset].matches('\n').count() + 1,
+            column: source[line_start..offset].chars().count() + 1,
+            snippet: source[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Converts a nom error into a parse error pointing at where parsing failed in `source`, the
+    /// full input that was handed to the parser.
+    pub fn from_nom<I: InputLength>(source: &str, err: nom::Err<nom::error::Error<I>>) -> Self {
+        match err {
+            nom::Err::Error(err) | nom::Err::Failure(err) => Error::parse(
+                source,
+                source.len().saturating_sub(err.input.input_len()),
+                format!("unexpected input ({})", err.code.description()),
+            ),
+            nom::Err::Incomplete(_) => {
+                Error::parse(source, source.len(), "unexpected end of input")
+            }
+        }
+    }
+
+    /// Wraps a failure to read the file at `path`, naming it in the message.
+    pub fn read(path: &Path, err: io::Error) -> Self {
+        Error::Io(io::Error::new(
+            err.kind(),
+            format!("Unable to read {}: {}", path.display(), err),
+        ))
+    }
+
+    pub fn no_solution(message: impl Into<String>) -> Self {
+        Error::NoSolution(message.into())
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Error::InvalidInput(message.into())
+    }
+
+    pub fn usage(message: impl Into<String>) -> Self {
+        Error::Usage(message.into())
+    }
+
+    pub fn http(message: impl Into<String>) -> Self {
+        Error::Http(message.into())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{}", err),
+            Error::Parse {
+                line,
+                column,
+                snippet,
+                message,
+            } => {
+                let gutter = " ".repeat(line.to_string().len());
+                writeln!(
+                    f,
+                    "Parse error at line {}, column {}: {}",
+                    line, column, message
+                )?;
+                writeln!(f, "{} |", gutter)?;
+                writeln!(f, "{} | {}", line, snippet)?;
+                write!(f, "{} | {}^", gutter, " ".repeat(column - 1))
+            }
+            Error::NoSolution(message) => write!(f, "No solution found: {}", message),
+            Error::InvalidInput(message) => write!(f, "Invalid input: {}", message),
+            Error::InputNotFound(err) => write!(f, "{}", err),
+            Error::Usage(message) => write!(f, "{}", message),
+            Error::Http(message) => write!(f, "Request failed: {}", message),
+        }
+    }
+}
+
+// `main` functions returning a `Result` report errors using `Debug`, so render them the same way as
+// `Display` rather than as a struct dump.
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::InputNotFound(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<NotFound> for Error {
+    fn from(err: NotFound) -> Self {
+        Error::InputNotFound(err)
+    }
+}
+
+/// Why a line of input failed to parse, and the byte offset into the line where it went wrong.
+/// Any displayable error converts into one pointing at the start of the line.
+pub struct LineError {
+    offset: usize,
+    message: String,
+}
+
+impl LineError {
+    pub fn at(offset: usize, message: impl Into<String>) -> Self {
+        LineError {
+            offset,
+            message: message.into(),
+        }
+    }
+}
+
+impl<E: fmt::Display> From<E> for LineError {
+    fn from(err: E) -> Self {
+        LineError::at(0, err.to_string())
+    }
+}
+
+/// Parses each line of `input` with `parse`, reporting the first failure at its line, and at the
+/// column the parser says it went wrong at.
+pub fn parse_lines<T, E, F>(input: &str, mut parse: F) -> Result<Vec<T>>
+where
+    E: Into<LineError>,
+    F: FnMut(&str) -> std::result::Result<T, E>,
+{
+    input
+        .lines()
+        .map(|line| {
+            parse(line).map_err(|err| {
+                let LineError { offset, message } = err.into();
+                let line_start = line.as_ptr() as usize - input.as_ptr() as usize;
+                Error::parse(input, line_start + offset.min(line.len()), message)
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,134 @@
+//! Golden-answer tests against the example inputs from each puzzle's description.
+//!
+//! Each day keeps its examples in an `examples/` directory alongside an `expected.toml` listing the
+//! known answers for each example input:
+//!
+//! ```toml
+//! [[example]]
+//! input = "part1.txt"
+//! part1 = 514579
+//! part2 = 241861950
+//! ```
+//!
+//! Either answer may be omitted when an example only applies to one part. The `example_tests!`
+//! macro generates a test for each part that checks every example listing an answer for it.
+
+use crate::{Answer, Error, Part, Result, Solution};
+use serde::Deserialize;
+use std::path::Path;
+
+#[derive(Deserialize)]
+struct Expected {
+    example: Vec<Example>,
+}
+
+#[derive(Deserialize)]
+struct Example {
+    input: String,
+    part1: Option<ExpectedAnswer>,
+    part2: Option<ExpectedAnswer>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ExpectedAnswer {
+    Number(i64),
+    Text(String),
+}
+
+impl From<ExpectedAnswer> for Answer {
+    fn from(expected: ExpectedAnswer) -> Self {
+        match expected {
+            ExpectedAnswer::Number(number) => Answer::from(number),
+            ExpectedAnswer::Text(text) => Answer::from(text),
+        }
+    }
+}
+
+/// Solves `part` of every example in `dir` that lists an answer for it, returning an error
+/// describing each example that produced the wrong answer or failed to solve.
+pub fn check<S: Solution>(dir: impl AsRef<Path>, part: Part) -> Result<()> {
+    let dir = dir.as_ref();
+    let expected_path = dir.join("expected.toml");
+    let expected = read(&expected_path)?;
+    let expected = toml::from_str::<Expected>(&expected).map_err(|err| match err.line_col() {
+        Some((line, column)) => Error::Parse {
+            line: line + 1,
+            column: column + 1,
+            snippet: expected.lines().nth(line).unwrap_or_default().to_string(),
+            message: err.to_string(),
+        },
+        None => Error::invalid_input(format!("{}: {}", expected_path.display(), err)),
+    })?;
+
+    let mut checked = 0;
+    let mut failures = Vec::new();
+    for example in expected.example {
+        let answer = match part {
+            Part::One => example.part1,
+            Part::Two => example.part2,
+        };
+        let expected_answer = match answer {
+            Some(answer) => Answer::from(answer),
+            None => continue,
+        };
+
+        checked += 1;
+        let input_path = dir.join(&example.input);
+        let input = read(&input_path)?;
+        match S::parse(&input).and_then(|input| S::solve(&input, part)) {
+            Ok(answer) if answer == expected_answer => (),
+            Ok(answer) => failures.push(format!(
+                "{}: expected {}, got {}",
+                example.input, expected_answer, answer
+            )),
+            Err(err) => failures.push(format!(
+                "{}: expected {}, got error: {}",
+                example.input, expected_answer, err
+            )),
+        }
+    }
+
+    if checked == 0 {
+        Err(Error::invalid_input(format!(
+            "{} has no examples for part {}",
+            expected_path.display(),
+            part
+        )))
+    } else if !failures.is_empty() {
+        Err(Error::invalid_input(format!(
+            "part {} failed:\n    {}",
+            part,
+            failures.join("\n    ")
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+fn read(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|err| Error::read(path, err))
+}
+
+/// Generates a `part1` and `part2` test checking a solution against the examples in the calling
+/// crate's `examples/` directory.
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:ty) => {
+        #[test]
+        fn part1() {
+            let examples = concat!(env!("CARGO_MANIFEST_DIR"), "/examples");
+            if let Err(err) = $crate::examples::check::<$solution>(examples, $crate::Part::One) {
+                panic!("{}", err);
+            }
+        }
+
+        #[test]
+        fn part2() {
+            let examples = concat!(env!("CARGO_MANIFEST_DIR"), "/examples");
+            if let Err(err) = $crate::examples::check::<$solution>(examples, $crate::Part::Two) {
+                panic!("{}", err);
+            }
+        }
+    };
+}
@@ -0,0 +1,308 @@
+//! A rectangular grid of cells, as used by the many puzzles whose input is a map drawn in text.
+//!
+//! Positions are `(x, y)` pairs with the origin at the top left, so `y` is the line of the input a
+//! cell came from and `x` its column.
+
+use crate::{Error, Result};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// The offsets of the four orthogonally adjacent cells, clockwise from north.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// The offsets of all eight surrounding cells, clockwise from north.
+pub const ALL_DIRECTIONS: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its rows, which must all be the same, non-zero width.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let (width, height) = (rows.first().map_or(0, Vec::len), rows.len());
+        if width == 0 {
+            return Err(Error::invalid_input("The grid is empty"));
+        }
+        if let Some(y) = rows.iter().position(|row| row.len() != width) {
+            return Err(Error::invalid_input(format!(
+                "Row {} is {} cells wide, expected {}",
+                y + 1,
+                rows[y].len(),
+                width
+            )));
+        }
+        Ok(Grid {
+            cells: rows.into_iter().flatten().collect(),
+            width,
+            height,
+        })
+    }
+
+    /// Parses a grid with a line of text per row, converting each character with `parse_cell`.
+    /// A character that fails to convert is reported as a parse error at its position.
+    pub fn parse<E, F>(input: &str, mut parse_cell: F) -> Result<Self>
+    where
+        E: fmt::Display,
+        F: FnMut(char) -> std::result::Result<T, E>,
+    {
+        let mut rows = Vec::new();
+        let mut offset = 0;
+        for line in input.split_inclusive('\n') {
+            let row = line
+                .trim_end_matches(&['\n', '\r'][..])
+                .char_indices()
+                .map(|(i, chr)| {
+                    parse_cell(chr).map_err(|err| Error::parse(input, offset + i, err.to_string()))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            rows.push(row);
+            offset += line.len();
+        }
+        Grid::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Every cell, row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    /// The index into `cells` of the cell at a position, which must be within the grid.
+    pub fn index_of(&self, x: usize, y: usize) -> usize {
+        debug_assert!(x < self.width && y < self.height);
+        x + y * self.width
+    }
+
+    /// The position of the cell at an index into `cells`.
+    pub fn position_of(&self, index: usize) -> (usize, usize) {
+        (index % self.width, index / self.width)
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if self.contains(x, y) {
+            Some(&self.cells[self.index_of(x, y)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.contains(x, y) {
+            let index = self.index_of(x, y);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// Offsets a position by `(dx, dy)`, returning `None` if that leaves the grid.
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        if self.contains(x, y) {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+
+    /// Gets a cell as if the grid repeated infinitely in every direction.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self[(x, y)]
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (self.position_of(i), cell))
+    }
+
+    /// The positions within the grid at each of `offsets` from `(x, y)`.
+    pub fn neighbours<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&offset| self.offset((x, y), offset))
+    }
+
+    /// The positions of the up to four cells orthogonally adjacent to `(x, y)`.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(x, y, &ORTHOGONAL)
+    }
+
+    /// The positions of the up to eight cells surrounding `(x, y)`.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(x, y, &ALL_DIRECTIONS)
+    }
+
+    /// The positions along a ray from `(x, y)` in `direction`, not including `(x, y)` itself, up to
+    /// the edge of the grid.
+    pub fn ray(
+        &self,
+        (x, y): (usize, usize),
+        direction: (isize, isize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        std::iter::successors(self.offset((x, y), direction), move |&position| {
+            self.offset(position, direction)
+        })
+    }
+
+    /// Casts a ray from `from` in `direction`, returning the first position whose cell `stops` the
+    /// ray, if any is reached within `max_distance` steps (or before the edge of the grid when
+    /// `max_distance` is `None`).
+    pub fn cast_ray<F>(
+        &self,
+        from: (usize, usize),
+        direction: (isize, isize),
+        max_distance: Option<usize>,
+        mut stops: F,
+    ) -> Option<(usize, usize)>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.ray(from, direction)
+            .take(max_distance.unwrap_or(usize::MAX))
+            .find(|&position| stops(&self[position]))
+    }
+
+    /// Precomputes, for every cell, the indices into `cells` of the cells it can see: the first
+    /// cell that `stops` a ray cast in each of `directions`. Lets simulations that repeatedly
+    /// look along the same lines of sight do the casting once.
+    pub fn visibility_table<F>(
+        &self,
+        directions: &[(isize, isize)],
+        max_distance: Option<usize>,
+        mut stops: F,
+    ) -> Grid<Vec<usize>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let cells = (0..self.cells.len())
+            .map(|index| {
+                let from = self.position_of(index);
+                directions
+                    .iter()
+                    .filter_map(|&direction| {
+                        self.cast_ray(from, direction, max_distance, &mut stops)
+                    })
+                    .map(|(x, y)| self.index_of(x, y))
+                    .collect()
+            })
+            .collect();
+        Grid {
+            cells,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(
+            self.contains(x, y),
+            "({}, {}) is outside of a {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        &self.cells[self.index_of(x, y)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(
+            self.contains(x, y),
+            "({}, {}) is outside of a {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        let index = self.index_of(x, y);
+        &mut self.cells[index]
+    }
+}
+
+/// Draws the grid with a line per row, the way it's usually given in puzzle input.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
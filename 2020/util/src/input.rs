@@ -0,0 +1,196 @@
+//! Locates puzzle inputs by year and day.
+//!
+//! Inputs live at `<year>/_<day>/input.txt` relative to the repository root (for example
+//! `2020/_07/input.txt`). When a root directory is configured, either through the `AOC_INPUT_DIR`
+//! environment variable or explicitly (typically from an `--input-dir` flag), only that root is
+//! searched. Otherwise the current directory and each of its ancestors are searched, followed by the
+//! workspace this crate was built from, so solutions can be run from anywhere inside the repository.
+
+use crate::{Error, Result};
+use std::env;
+use std::fmt;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// The environment variable used to configure the input root directory.
+pub const ROOT_ENV_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where a puzzle input will be read from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Returned when none of the candidate paths for an input exist.
+#[derive(Debug)]
+pub struct NotFound {
+    pub year: u16,
+    pub day: u8,
+    pub tried: Vec<PathBuf>,
+}
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Unable to find the input for {} day {}, tried:",
+            self.year, self.day
+        )?;
+        for path in &self.tried {
+            write!(f, "\n    {}", path.display())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for NotFound {}
+
+#[derive(Clone, Debug, Default)]
+pub struct Locator {
+    input: Option<Source>,
+    root: Option<PathBuf>,
+}
+
+impl Locator {
+    /// Creates a locator whose root directory is taken from `AOC_INPUT_DIR`, if set.
+    pub fn from_env() -> Self {
+        Locator {
+            input: None,
+            root: env::var_os(ROOT_ENV_VAR)
+                .filter(|root| !root.is_empty())
+                .map(PathBuf::from),
+        }
+    }
+
+    /// Only search for inputs under `root`, overriding `AOC_INPUT_DIR`.
+    pub fn root<P: Into<PathBuf>>(mut self, root: P) -> Self {
+        self.root = Some(root.into());
+        self
+    }
+
+    /// Read the input from `path` rather than searching for it. A path of `-` reads from stdin.
+    pub fn input<P: AsRef<Path>>(mut self, path: P) -> Self {
+        let path = path.as_ref();
+        self.input = Some(if path == Path::new("-") {
+            Source::Stdin
+        } else {
+            Source::File(path.to_path_buf())
+        });
+        self
+    }
+
+    /// Returns true if an explicit input was given, which applies to every day.
+    pub fn has_explicit_input(&self) -> bool {
+        self.input.is_some()
+    }
+
+    /// The directory downloaded inputs are cached under: the configured root if there is one,
+    /// otherwise the repository containing this workspace. Each year has its own directory
+    /// beneath it.
+    pub fn cache_root(&self) -> PathBuf {
+        match &self.root {
+            Some(root) => root.clone(),
+            None => {
+                let workspace_root = workspace_root();
+                workspace_root
+                    .parent()
+                    .map_or(workspace_root.clone(), Path::to_path_buf)
+            }
+        }
+    }
+
+    /// Where a downloaded input for the given day is cached, which is always the first candidate
+    /// searched under the cache root.
+    pub fn cache_path(&self, year: u16, day: u8) -> PathBuf {
+        self.cache_root()
+            .join(year.to_string())
+            .join(format!("_{:02}", day))
+            .join("input.txt")
+    }
+
+    /// Returns every path that will be tried for the given day, in order.
+    pub fn candidates(&self, year: u16, day: u8) -> Vec<PathBuf> {
+        let search_dirs = match &self.root {
+            Some(root) => vec![root.clone()],
+            None => {
+                let mut dirs = env::current_dir()
+                    .map(|cwd| cwd.ancestors().map(Path::to_path_buf).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                dirs.push(workspace_root());
+                dirs
+            }
+        };
+
+        let day_dir = format!("_{:02}", day);
+        let mut candidates = Vec::new();
+        for dir in search_dirs {
+            for candidate in &[
+                dir.join(year.to_string()).join(&day_dir).join("input.txt"),
+                dir.join(&day_dir).join("input.txt"),
+            ] {
+                if !candidates.contains(candidate) {
+                    candidates.push(candidate.clone());
+                }
+            }
+        }
+        candidates
+    }
+
+    pub fn locate(&self, year: u16, day: u8) -> std::result::Result<Source, NotFound> {
+        if let Some(input) = &self.input {
+            return match input {
+                Source::File(path) if !path.is_file() => Err(NotFound {
+                    year,
+                    day,
+                    tried: vec![path.clone()],
+                }),
+                _ => Ok(input.clone()),
+            };
+        }
+
+        let tried = self.candidates(year, day);
+        match tried.iter().find(|candidate| candidate.is_file()) {
+            Some(found) => Ok(Source::File(found.clone())),
+            None => Err(NotFound { year, day, tried }),
+        }
+    }
+
+    pub fn read(&self, year: u16, day: u8) -> Result<String> {
+        match self.locate(year, day)? {
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            Source::File(path) => {
+                std::fs::read_to_string(&path).map_err(|err| Error::read(&path, err))
+            }
+        }
+    }
+}
+
+/// The directory containing the workspace this crate was built in (e.g. `2020/`).
+fn workspace_root() -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    manifest_dir.parent().unwrap_or(manifest_dir).to_path_buf()
+}
+
+/// Reads the input for a day binary: the first command line argument (a path, or `-` for stdin)
+/// if given, otherwise the input found by searching from `AOC_INPUT_DIR` or the workspace.
+pub fn load(year: u16, day: u8) -> Result<String> {
+    let mut locator = Locator::from_env();
+    if let Some(input) = env::args_os().nth(1) {
+        locator = locator.input(input);
+    }
+    locator.read(year, day)
+}
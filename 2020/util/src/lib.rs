@@ -1,17 +1,24 @@
-use std::error::Error;
 use std::fs::File;
 use std::io::{self, BufRead};
 use std::path::Path;
 
-pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+pub mod automaton;
+pub mod bench;
+mod error;
+pub mod examples;
+pub mod grid;
+pub mod input;
+mod solution;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+pub use error::{parse_lines, Error, LineError, Result};
+pub use solution::{Answer, Part, Solution};
 
 pub fn read_lines<P>(filename: P) -> io::Result<impl Iterator<Item = String>>
 where
     P: AsRef<Path>,
 {
     let file = File::open(filename)?;
-    Ok(io::BufReader::new(file)
-        .lines()
-        .into_iter()
-        .filter_map(|line| line.ok()))
+    Ok(io::BufReader::new(file).lines().map_while(|line| line.ok()))
 }
@@ -0,0 +1,112 @@
+use crate::Result;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// One of the two halves of a puzzle.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// The answer to one part of a puzzle. Most answers are numbers, but some puzzles ask for a string.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(s.parse::<i128>()
+            .map(Answer::Number)
+            .unwrap_or_else(|_| Answer::Text(s.to_string())))
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(number: $ty) -> Self {
+                    Answer::Number(i128::from(number))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl From<usize> for Answer {
+    fn from(number: usize) -> Self {
+        // usize is at most 64 bits on every supported platform
+        Answer::Number(i128::try_from(number).unwrap_or(i128::MAX))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+/// A puzzle solution. The input is parsed once and then shared by both parts.
+pub trait Solution {
+    /// The parsed puzzle input, which may borrow from the raw input text.
+    type Input<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>>;
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer>;
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer>;
+
+    fn solve(input: &Self::Input<'_>, part: Part) -> Result<Answer> {
+        match part {
+            Part::One => Self::part1(input),
+            Part::Two => Self::part2(input),
+        }
+    }
+
+    /// Parses `input` and solves each of `parts`, in order. A part that fails to solve doesn't
+    /// prevent the remaining parts from being solved.
+    fn answers(input: &str, parts: &[Part]) -> Result<Vec<Result<Answer>>> {
+        let input = Self::parse(input)?;
+        Ok(parts
+            .iter()
+            .map(|part| Self::solve(&input, *part))
+            .collect())
+    }
+}
@@ -0,0 +1,177 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use util::automaton::{Automaton, Neighbourhood, Outcome, Rule, Stepping};
+use util::grid::Grid;
+
+struct Life;
+
+impl Rule for Life {
+    type Cell = bool;
+
+    fn is_live(&self, cell: &bool) -> bool {
+        *cell
+    }
+
+    fn next(&self, cell: &bool, live_neighbours: usize) -> bool {
+        matches!((cell, live_neighbours), (true, 2) | (_, 3))
+    }
+}
+
+fn life(pattern: &str) -> Automaton<Life> {
+    let cells = Grid::parse(pattern, |chr| Ok::<_, &str>(chr == '#')).unwrap();
+    Automaton::new(Neighbourhood::moore(&cells), cells, Life)
+}
+
+#[test]
+fn still_lifes_are_stable_immediately() {
+    let mut block = life("....\n.##.\n.##.\n....");
+    assert_eq!(block.step(), 0);
+    assert_eq!(block.run(10), Outcome::Stable { generation: 0 });
+    assert_eq!(block.generation(), 0);
+}
+
+#[test]
+fn counts_generations_until_stable() {
+    // Three cells in an L become a block after a generation
+    let mut automaton = life("....\n.##.\n.#..\n....");
+    assert_eq!(automaton.step(), 1);
+    assert_eq!(automaton.generation(), 1);
+    assert_eq!(automaton.run(10), Outcome::Stable { generation: 1 });
+    assert_eq!(
+        automaton.cells().to_string(),
+        life("....\n.##.\n.##.\n....").cells().to_string()
+    );
+}
+
+#[test]
+fn detects_cycles() {
+    let mut blinker = life(".....\n.....\n.###.\n.....\n.....");
+    blinker.step();
+    assert_eq!(
+        blinker.run(10),
+        Outcome::Cycle {
+            start: 1,
+            period: 2
+        }
+    );
+}
+
+#[test]
+fn stops_at_the_generation_limit() {
+    // A glider travels until it hits the edge, which takes more than 3 generations
+    let mut glider = life(".#......\n..#.....\n###.....\n........\n........\n........");
+    assert_eq!(glider.run(3), Outcome::Limit);
+    assert_eq!(glider.generation(), 3);
+}
+
+#[test]
+fn uses_the_given_neighbourhood() {
+    struct Spread;
+
+    impl Rule for Spread {
+        type Cell = bool;
+
+        fn is_live(&self, cell: &bool) -> bool {
+            *cell
+        }
+
+        fn next(&self, cell: &bool, live_neighbours: usize) -> bool {
+            *cell || live_neighbours > 0
+        }
+    }
+
+    let cells = Grid::parse("...\n.#.\n...", |chr| Ok::<_, &str>(chr == '#')).unwrap();
+    let mut spread = Automaton::new(Neighbourhood::von_neumann(&cells), cells, Spread);
+    spread.step();
+    assert_eq!(
+        spread
+            .cells()
+            .map(|&cell| if cell { '#' } else { '.' })
+            .to_string(),
+        ".#.\n###\n.#."
+    );
+    assert_eq!(spread.run(10), Outcome::Stable { generation: 2 });
+}
+
+#[test]
+fn incremental_stepping_matches_full_stepping() {
+    let pattern = ".#......\n..#.....\n###.....\n........\n.....##.\n.....##.";
+    let mut full = life(pattern);
+    let mut incremental = life(pattern).with_stepping(Stepping::Incremental);
+    for _ in 0..20 {
+        assert_eq!(incremental.step(), full.step());
+        assert_eq!(incremental.cells(), full.cells());
+    }
+    assert_eq!(incremental.generation(), full.generation());
+
+    let mut blinker =
+        life(".....\n.....\n.###.\n.....\n.....").with_stepping(Stepping::Incremental);
+    assert_eq!(
+        blinker.run(10),
+        Outcome::Cycle {
+            start: 0,
+            period: 2
+        }
+    );
+}
+
+/// Life, counting how many cells it has worked out. An `Rc` can't be shared between threads, so
+/// this rule can only be stepped sequentially.
+struct CountingLife {
+    updates: Rc<Cell<usize>>,
+}
+
+impl Rule for CountingLife {
+    type Cell = bool;
+
+    fn is_live(&self, cell: &bool) -> bool {
+        *cell
+    }
+
+    fn next(&self, cell: &bool, live_neighbours: usize) -> bool {
+        self.updates.set(self.updates.get() + 1);
+        Life.next(cell, live_neighbours)
+    }
+}
+
+#[test]
+fn sequential_stepping_doesnt_need_a_thread_safe_rule() {
+    let cells = Grid::parse("....\n###.\n....", |chr| Ok::<_, &str>(chr == '#')).unwrap();
+    for &stepping in &[Stepping::Full, Stepping::Incremental] {
+        let updates = Rc::new(Cell::new(0));
+        let rule = CountingLife {
+            updates: Rc::clone(&updates),
+        };
+        let mut blinker = Automaton::new(Neighbourhood::moore(&cells), cells.clone(), rule)
+            .with_stepping(stepping);
+        assert_eq!(blinker.step(), 4);
+        assert!(updates.get() > 0);
+    }
+}
+
+#[test]
+fn parallel_stepping_matches_full_stepping() {
+    let pattern = ".#......\n..#.....\n###.....\n........\n.....##.\n.....##.";
+    let mut full = life(pattern);
+    let mut parallel = [1, 2, 4, 100]
+        .iter()
+        .map(|&threads| life(pattern).with_parallel_stepping(threads))
+        .collect::<Vec<_>>();
+    for _ in 0..20 {
+        let changed = full.step();
+        for parallel in &mut parallel {
+            assert_eq!(parallel.step(), changed);
+            assert_eq!(parallel.cells(), full.cells());
+        }
+    }
+}
+
+#[test]
+fn flattens_a_visibility_table() {
+    let grid = Grid::parse("#..\n.#.\n..#\n.##", Ok::<_, &str>).unwrap();
+    let table = grid.visibility_table(&util::grid::ALL_DIRECTIONS, None, |&chr| chr == '#');
+    let neighbourhood = Neighbourhood::from_table(&table);
+    for (index, visible) in table.cells().iter().enumerate() {
+        assert_eq!(neighbourhood.of(index), &visible[..]);
+    }
+}
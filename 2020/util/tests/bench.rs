@@ -0,0 +1,39 @@
+use std::time::Duration;
+use util::bench::Stats;
+
+fn millis(samples: &[u64]) -> Vec<Duration> {
+    samples.iter().copied().map(Duration::from_millis).collect()
+}
+
+#[test]
+fn takes_the_middle_sample_of_an_odd_number() {
+    assert_eq!(
+        Stats::from_samples(millis(&[9, 1, 4])),
+        Stats {
+            min: Duration::from_millis(1),
+            median: Duration::from_millis(4),
+            max: Duration::from_millis(9),
+        }
+    );
+    let seven = Duration::from_millis(7);
+    assert_eq!(
+        Stats::from_samples(vec![seven]),
+        Stats {
+            min: seven,
+            median: seven,
+            max: seven,
+        }
+    );
+}
+
+#[test]
+fn averages_the_middle_two_samples_of_an_even_number() {
+    assert_eq!(
+        Stats::from_samples(millis(&[10, 2, 8, 3])),
+        Stats {
+            min: Duration::from_millis(2),
+            median: Duration::from_micros(5500),
+            max: Duration::from_millis(10),
+        }
+    );
+}
@@ -0,0 +1,69 @@
+use util::Error;
+
+fn location(err: Error) -> (usize, usize, String) {
+    match err {
+        Error::Parse {
+            line,
+            column,
+            snippet,
+            ..
+        } => (line, column, snippet),
+        err => panic!("expected a parse error, got {}", err),
+    }
+}
+
+#[test]
+fn locates_parse_errors_by_line_and_column() {
+    let source = "abc\ndef\n";
+    assert_eq!(
+        location(Error::parse(source, 5, "bad")),
+        (2, 2, "def".to_string())
+    );
+    assert_eq!(
+        location(Error::parse(source, 100, "bad")),
+        (3, 1, String::new())
+    );
+}
+
+#[test]
+fn points_at_the_character_an_offset_falls_inside() {
+    // The `é` takes bytes 4 and 5, so offset 5 is in the middle of it
+    let source = "1-3 é: abc\n";
+    assert!(!source.is_char_boundary(5));
+    assert_eq!(
+        location(Error::parse(source, 5, "bad")),
+        (1, 5, "1-3 é: abc".to_string())
+    );
+}
+
+#[test]
+fn parse_lines_reports_the_column_the_parser_gives() {
+    let err = util::parse_lines("12\n1a3\n", |line| {
+        match line.bytes().position(|chr| !chr.is_ascii_digit()) {
+            Some(offset) => Err(util::LineError::at(offset, "not a digit")),
+            None => Ok(line.len()),
+        }
+    })
+    .unwrap_err();
+    assert_eq!(location(err), (2, 2, "1a3".to_string()));
+
+    // Errors that don't say where they went wrong point at the start of the line
+    let err = util::parse_lines("1\r\n2\r\nx\r\n", str::parse::<u8>).unwrap_err();
+    assert_eq!(location(err), (3, 1, "x".to_string()));
+}
+
+#[test]
+fn read_errors_name_the_file() {
+    let path = std::path::Path::new("no/such/input.txt");
+    let err = std::fs::read_to_string(path).unwrap_err();
+    let kind = err.kind();
+    match Error::read(path, err) {
+        Error::Io(err) => {
+            assert_eq!(err.kind(), kind);
+            assert!(err
+                .to_string()
+                .starts_with("Unable to read no/such/input.txt: "));
+        }
+        err => panic!("expected an io error, got {}", err),
+    }
+}
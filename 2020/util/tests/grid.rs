@@ -0,0 +1,108 @@
+use util::grid::Grid;
+use util::Error;
+
+const MAP: &str = "#..\n.#.\n..#\n.##\n";
+
+fn map() -> Grid<char> {
+    Grid::parse(MAP, Ok::<_, &str>).unwrap()
+}
+
+#[test]
+fn parses_rows_of_text() {
+    let grid = map();
+    assert_eq!((grid.width(), grid.height()), (3, 4));
+    assert_eq!(grid[(1, 1)], '#');
+    assert_eq!(grid.get(2, 3), Some(&'#'));
+    assert_eq!(grid.get(3, 0), None);
+    assert_eq!(grid.get(0, 4), None);
+    assert_eq!(grid.to_string(), MAP.trim_end());
+}
+
+#[test]
+fn reports_where_a_cell_failed_to_parse() {
+    let err = Grid::parse("..#\n.x.\n", |chr| match chr {
+        '.' | '#' => Ok(chr),
+        _ => Err("unknown cell"),
+    })
+    .unwrap_err();
+    match err {
+        Error::Parse { line, column, .. } => assert_eq!((line, column), (2, 2)),
+        err => panic!("unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn rejects_ragged_and_empty_grids() {
+    assert!(Grid::parse("..#\n.#\n", Ok::<_, &str>).is_err());
+    assert!(Grid::parse("", Ok::<_, &str>).is_err());
+}
+
+#[test]
+fn wraps_in_every_direction() {
+    let grid = map();
+    assert_eq!(*grid.get_wrapping(4, 1), '#');
+    assert_eq!(*grid.get_wrapping(-1, -1), '#');
+    assert_eq!(*grid.get_wrapping(-2, 5), '#');
+}
+
+#[test]
+fn iterates_rows_and_columns() {
+    let grid = map();
+    assert_eq!(grid.row(3), &['.', '#', '#']);
+    assert_eq!(grid.rows().count(), 4);
+    assert_eq!(grid.column(1).collect::<String>(), ".#.#");
+    let trees_per_column = grid
+        .columns()
+        .map(|column| column.filter(|&&chr| chr == '#').count())
+        .collect::<Vec<_>>();
+    assert_eq!(trees_per_column, vec![1, 2, 2]);
+}
+
+#[test]
+fn finds_neighbours_within_the_grid() {
+    let grid = map();
+    assert_eq!(
+        grid.neighbours4(0, 0).collect::<Vec<_>>(),
+        vec![(1, 0), (0, 1)]
+    );
+    assert_eq!(grid.neighbours8(0, 0).count(), 3);
+    assert_eq!(grid.neighbours8(1, 1).count(), 8);
+    assert_eq!(grid.neighbours4(1, 1).count(), 4);
+    assert_eq!(grid.neighbours8(2, 3).count(), 3);
+}
+
+#[test]
+fn casts_rays_to_the_first_stopping_cell() {
+    let grid = map();
+    let is_tree = |&chr: &char| chr == '#';
+    assert_eq!(grid.cast_ray((0, 0), (1, 1), None, is_tree), Some((1, 1)));
+    assert_eq!(grid.cast_ray((0, 3), (1, 0), None, is_tree), Some((1, 3)));
+    assert_eq!(grid.cast_ray((0, 1), (1, 0), None, is_tree), Some((1, 1)));
+    assert_eq!(grid.cast_ray((0, 2), (1, 0), None, is_tree), Some((2, 2)));
+    assert_eq!(grid.cast_ray((0, 2), (1, 0), Some(1), is_tree), None);
+    assert_eq!(grid.cast_ray((2, 0), (0, -1), None, is_tree), None);
+    assert_eq!(
+        grid.ray((0, 0), (1, 1)).collect::<Vec<_>>(),
+        vec![(1, 1), (2, 2)]
+    );
+}
+
+#[test]
+fn precomputes_what_each_cell_can_see() {
+    let grid = map();
+    let visible = grid.visibility_table(&util::grid::ALL_DIRECTIONS, None, |&chr| chr == '#');
+    let positions = |x, y| {
+        let mut positions = visible[(x, y)]
+            .iter()
+            .map(|&index| grid.position_of(index))
+            .collect::<Vec<_>>();
+        positions.sort_unstable();
+        positions
+    };
+    assert_eq!(positions(0, 0), vec![(1, 1)]);
+    assert_eq!(positions(0, 2), vec![(0, 0), (1, 1), (1, 3), (2, 2)]);
+
+    let adjacent = grid.visibility_table(&util::grid::ALL_DIRECTIONS, Some(1), |_| true);
+    assert_eq!(adjacent[(1, 1)].len(), 8);
+    assert_eq!(adjacent[(0, 0)].len(), 3);
+}
@@ -0,0 +1,118 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use util::input::{Locator, Source, ROOT_ENV_VAR};
+use util::testing::TempDir;
+
+/// Creates `path` under `root`, with its parent directories, holding `contents`.
+fn write(root: &Path, path: &str, contents: &str) -> PathBuf {
+    let path = root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn searches_the_current_directory_and_its_ancestors_then_the_workspace() {
+    let cwd = env::current_dir().unwrap();
+    let mut expected = Vec::new();
+    for dir in cwd.ancestors() {
+        for candidate in &[
+            dir.join("2020").join("_07").join("input.txt"),
+            dir.join("_07").join("input.txt"),
+        ] {
+            // A year directory's `_07` is also its parent's `2020/_07`, which is only tried once
+            if !expected.contains(candidate) {
+                expected.push(candidate.clone());
+            }
+        }
+    }
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    assert!(expected.contains(&workspace.join("_07").join("input.txt")));
+    // Tests run from the crate's directory, so the workspace is already one of its ancestors and
+    // isn't searched a second time
+    assert_eq!(Locator::default().candidates(2020, 7), expected);
+}
+
+#[test]
+fn searches_only_the_root_when_one_is_given() {
+    let dir = TempDir::new();
+    assert_eq!(
+        Locator::default().root(dir.path()).candidates(2020, 7),
+        vec![
+            dir.path().join("2020").join("_07").join("input.txt"),
+            dir.path().join("_07").join("input.txt"),
+        ]
+    );
+}
+
+#[test]
+fn takes_the_root_from_the_environment_unless_overridden() {
+    let (from_env, explicit) = (TempDir::new(), TempDir::new());
+    env::set_var(ROOT_ENV_VAR, from_env.path());
+    let locator = Locator::from_env();
+    env::remove_var(ROOT_ENV_VAR);
+
+    assert_eq!(
+        locator.candidates(2020, 7)[0],
+        from_env.path().join("2020").join("_07").join("input.txt")
+    );
+    assert_eq!(
+        locator.root(explicit.path()).candidates(2020, 7)[0],
+        explicit.path().join("2020").join("_07").join("input.txt")
+    );
+}
+
+#[test]
+fn finds_the_first_candidate_that_exists() {
+    let dir = TempDir::new();
+    let locator = Locator::default().root(dir.path());
+    let day_only = write(dir.path(), "_07/input.txt", "day only\n");
+    assert_eq!(locator.locate(2020, 7).unwrap(), Source::File(day_only));
+    assert_eq!(locator.read(2020, 7).unwrap(), "day only\n");
+
+    let with_year = write(dir.path(), "2020/_07/input.txt", "with year\n");
+    assert_eq!(locator.locate(2020, 7).unwrap(), Source::File(with_year));
+}
+
+#[test]
+fn lists_every_path_tried_when_nothing_is_found() {
+    let dir = TempDir::new();
+    let err = Locator::default()
+        .root(dir.path())
+        .locate(2020, 7)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Unable to find the input for 2020 day 7, tried:\n    {}\n    {}",
+            dir.path().join("2020/_07/input.txt").display(),
+            dir.path().join("_07/input.txt").display()
+        )
+    );
+}
+
+#[test]
+fn reads_an_explicit_input_instead_of_searching() {
+    let dir = TempDir::new();
+    let path = write(dir.path(), "mine.txt", "mine\n");
+    let locator = Locator::default().root(dir.path()).input(&path);
+    assert!(locator.has_explicit_input());
+    assert_eq!(locator.read(2020, 7).unwrap(), "mine\n");
+
+    let missing = dir.path().join("missing.txt");
+    let err = Locator::default()
+        .input(&missing)
+        .locate(2020, 7)
+        .unwrap_err();
+    assert_eq!(err.tried, vec![missing]);
+}
+
+#[test]
+fn treats_a_dash_as_stdin() {
+    let locator = Locator::default().input("-");
+    assert!(locator.has_explicit_input());
+    assert_eq!(locator.locate(2020, 7).unwrap(), Source::Stdin);
+    assert_eq!(Source::Stdin.to_string(), "<stdin>");
+    assert!(!Locator::default().has_explicit_input());
+}
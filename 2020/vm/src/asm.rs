@@ -0,0 +1,64 @@
+//! Assembles programs written the way the puzzle input writes them: an instruction per line, its
+//! mnemonic followed by a signed argument, as in `jmp -3`.
+//!
+//! An instruction may also be labelled with its position, as in `12: jmp -3`, and anything after a
+//! `#` is a comment, which is how the [disassembler](crate::disasm) annotates programs.
+
+use crate::isa::{Instr, Op};
+use util::{Error, Result};
+
+/// Assembles a program, reporting the line and column of the first thing that isn't a valid
+/// instruction. Blank lines and lines with only a comment are skipped.
+pub fn assemble(source: &str) -> Result<Vec<Instr>> {
+    let mut program = Vec::new();
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        let text = line.trim_end_matches(&['\n', '\r'][..]);
+        let text = text.split('#').next().unwrap_or_default();
+        if !text.trim().is_empty() {
+            program.push(instruction(source, offset, text, program.len())?);
+        }
+        offset += line.len();
+    }
+    Ok(program)
+}
+
+/// Assembles the instruction at position `pc` on a line starting at byte `offset` into `source`.
+fn instruction(source: &str, offset: usize, line: &str, pc: usize) -> Result<Instr> {
+    let mut tokens = tokens(line).peekable();
+    let error = |column: usize, message: String| Error::parse(source, offset + column, message);
+
+    if let Some((column, label)) = tokens.next_if(|(_, token)| token.ends_with(':')) {
+        let label = &label[..label.len() - 1];
+        if label.parse::<usize>().ok() != Some(pc) {
+            return Err(error(
+                column,
+                format!("the label `{}` doesn't match the position {}", label, pc),
+            ));
+        }
+    }
+    let (column, mnemonic) = tokens
+        .next()
+        .ok_or_else(|| error(line.len(), "expected an instruction".to_string()))?;
+    let op = Op::from_mnemonic(mnemonic)
+        .ok_or_else(|| error(column, format!("unknown opcode `{}`", mnemonic)))?;
+    let (column, arg) = tokens.next().ok_or_else(|| {
+        error(
+            line.len(),
+            format!("`{}` expects an argument", op.mnemonic()),
+        )
+    })?;
+    let arg = arg
+        .parse::<i64>()
+        .map_err(|err| error(column, format!("invalid argument `{}`: {}", arg, err)))?;
+    if let Some((column, extra)) = tokens.next() {
+        return Err(error(column, format!("unexpected `{}`", extra)));
+    }
+    Ok(Instr::new(op, arg))
+}
+
+/// The whitespace-separated tokens on a line, with their byte offsets into it.
+fn tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    line.split_whitespace()
+        .map(move |token| (token.as_ptr() as usize - line.as_ptr() as usize, token))
+}
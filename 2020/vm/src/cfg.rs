@@ -0,0 +1,292 @@
+//! Static analysis of console programs through their control-flow graph.
+//!
+//! The console has no conditional branches, so every instruction has exactly one successor and a
+//! program's control-flow graph is a chain of basic blocks that either reaches the end of the
+//! program, leaves it, or closes into a loop. That makes it possible to say, without running
+//! anything, what every instruction's fate is and what a run from the start does.
+
+use crate::isa::{Instr, Op};
+use std::convert::TryFrom;
+use std::fmt::Write;
+
+/// A run of instructions that always execute together, from the first to the last.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Block {
+    /// The position of the first instruction.
+    pub start: usize,
+    /// The position just past the last instruction.
+    pub end: usize,
+    pub successor: Successor,
+}
+
+/// Where control goes after a block's last instruction.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Successor {
+    /// The block at this index into [`Cfg::blocks`].
+    Block(usize),
+    /// The end of the program, terminating it.
+    End,
+    /// A position outside of the program.
+    OutOfRange(i64),
+}
+
+/// What eventually happens to a program run from the start of a block.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Fate {
+    Terminates,
+    /// Control reaches a loop that starts at the block with this index, and never leaves it.
+    Loops(usize),
+    /// Control jumps to this position outside of the program.
+    LeavesProgram(i64),
+}
+
+/// How a run from the start of the program goes, worked out statically.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EntryPath {
+    /// The blocks run, in order, up to where the run ends or first repeats a block.
+    pub blocks: Vec<usize>,
+    pub fate: Fate,
+    /// The accumulator once every block on the path has run once.
+    pub acc: i64,
+}
+
+pub struct Cfg<'a> {
+    program: &'a [Instr],
+    blocks: Vec<Block>,
+    /// The index of the block each instruction belongs to.
+    block_of: Vec<usize>,
+    fates: Vec<Fate>,
+}
+
+impl<'a> Cfg<'a> {
+    pub fn new(program: &'a [Instr]) -> Self {
+        let end = program.len();
+        let target = |pc: usize| program[pc].effect(pc as i64).pc;
+
+        // A block starts at the start of the program, at every jump target, and after every jump
+        let mut leaders = vec![false; end];
+        if end > 0 {
+            leaders[0] = true;
+        }
+        for (pc, instr) in program.iter().enumerate() {
+            if instr.op == Op::Jmp {
+                if let Some(leader) = leaders.get_mut(pc + 1) {
+                    *leader = true;
+                }
+                if let Some(leader) = usize::try_from(target(pc))
+                    .ok()
+                    .and_then(|target| leaders.get_mut(target))
+                {
+                    *leader = true;
+                }
+            }
+        }
+
+        let mut block_of = vec![0; end];
+        let mut starts = Vec::new();
+        for pc in 0..end {
+            if leaders[pc] {
+                starts.push(pc);
+            }
+            block_of[pc] = starts.len() - 1;
+        }
+        let blocks = starts
+            .iter()
+            .enumerate()
+            .map(|(index, &start)| {
+                let block_end = starts.get(index + 1).copied().unwrap_or(end);
+                let next = target(block_end - 1);
+                let successor = match usize::try_from(next) {
+                    Ok(next) if next == end => Successor::End,
+                    Ok(next) if next < end => Successor::Block(block_of[next]),
+                    _ => Successor::OutOfRange(next),
+                };
+                Block {
+                    start,
+                    end: block_end,
+                    successor,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let fates = fates(&blocks);
+        Cfg {
+            program,
+            blocks,
+            block_of,
+            fates,
+        }
+    }
+
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    /// The index of the block containing the instruction at `pc`.
+    pub fn block_of(&self, pc: usize) -> usize {
+        self.block_of[pc]
+    }
+
+    /// What eventually happens when running from the start of each block.
+    pub fn fates(&self) -> &[Fate] {
+        &self.fates
+    }
+
+    /// The run from the start of the program, or `None` if the program is empty.
+    pub fn entry_path(&self) -> Option<EntryPath> {
+        if self.blocks.is_empty() {
+            return None;
+        }
+        let mut visited = vec![false; self.blocks.len()];
+        let (mut blocks, mut acc, mut block) = (Vec::new(), 0_i64, 0);
+        loop {
+            visited[block] = true;
+            blocks.push(block);
+            let Block { start, end, .. } = self.blocks[block];
+            acc = (start..end).fold(acc, |acc, pc| {
+                acc.wrapping_add(self.program[pc].effect(pc as i64).acc)
+            });
+            match self.blocks[block].successor {
+                Successor::Block(next) if !visited[next] => block = next,
+                _ => break,
+            }
+        }
+        Some(EntryPath {
+            blocks,
+            fate: self.fates[0],
+            acc,
+        })
+    }
+
+    /// The positions of the instructions a run from the start of the program never reaches.
+    pub fn unreachable(&self) -> Vec<usize> {
+        let reached = self.reached();
+        self.positions(|block| !reached[block])
+    }
+
+    /// The positions of the instructions from which the program can never terminate, whether
+    /// because it loops or because it leaves the program.
+    pub fn never_terminates(&self) -> Vec<usize> {
+        self.positions(|block| self.fates[block] != Fate::Terminates)
+    }
+
+    /// The jumps whose targets are outside of the program and not its end, with their targets.
+    pub fn out_of_range(&self) -> Vec<(usize, i64)> {
+        self.blocks
+            .iter()
+            .filter_map(|block| match block.successor {
+                Successor::OutOfRange(target) => Some((block.end - 1, target)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether a run from the start of the program reaches each block.
+    fn reached(&self) -> Vec<bool> {
+        let mut reached = vec![false; self.blocks.len()];
+        for block in self
+            .entry_path()
+            .map(|path| path.blocks)
+            .unwrap_or_default()
+        {
+            reached[block] = true;
+        }
+        reached
+    }
+
+    fn positions<F: Fn(usize) -> bool>(&self, include: F) -> Vec<usize> {
+        (0..self.program.len())
+            .filter(|&pc| include(self.block_of[pc]))
+            .collect()
+    }
+
+    /// Draws the graph in Graphviz's DOT language, a box per block. The path run from the start of
+    /// the program is drawn in bold, with the edge closing the loop it gets stuck in, if any, in
+    /// red. Blocks the path never reaches are greyed out, and those it does that can never
+    /// terminate are outlined in red.
+    pub fn to_dot(&self) -> String {
+        let on_path = self.reached();
+        // The edge that closes the loop a run from the start gets stuck in
+        let looping_from = self
+            .entry_path()
+            .filter(|path| matches!(path.fate, Fate::Loops(_)))
+            .and_then(|path| path.blocks.last().copied());
+        let mut dot = String::new();
+        let _ = writeln!(dot, "digraph program {{");
+        let _ = writeln!(dot, "    node [shape=box, fontname=monospace];");
+        let _ = writeln!(dot, "    start [shape=point];");
+        let _ = writeln!(dot, "    end [shape=doublecircle, label=\"end\"];");
+        if !self.blocks.is_empty() {
+            let _ = writeln!(dot, "    start -> b0 [style=bold];");
+        }
+        for (index, block) in self.blocks.iter().enumerate() {
+            let label = (block.start..block.end)
+                .map(|pc| format!("{}: {}\\l", pc, self.program[pc]))
+                .collect::<String>();
+            let mut attributes = vec![format!("label=\"{}\"", label)];
+            if !on_path[index] {
+                attributes.push("style=dashed, fontcolor=grey, color=grey".to_string());
+            } else if self.fates[index] != Fate::Terminates {
+                attributes.push("color=red".to_string());
+            }
+            let _ = writeln!(dot, "    b{} [{}];", index, attributes.join(", "));
+
+            let target = match block.successor {
+                Successor::Block(next) => format!("b{}", next),
+                Successor::End => "end".to_string(),
+                Successor::OutOfRange(target) => {
+                    let _ = writeln!(
+                        dot,
+                        "    out{} [shape=octagon, color=red, label=\"pc {}\"];",
+                        index, target
+                    );
+                    format!("out{}", index)
+                }
+            };
+            let style = if looping_from == Some(index) {
+                " [style=bold, color=red]"
+            } else if on_path[index] {
+                " [style=bold]"
+            } else {
+                ""
+            };
+            let _ = writeln!(dot, "    b{} -> {}{};", index, target, style);
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Works out each block's fate by following successors until reaching a block whose fate is
+/// known, or one already on the current walk, which closes a loop. Every block is walked once.
+fn fates(blocks: &[Block]) -> Vec<Fate> {
+    // The walk each block was first reached on, so a walk can tell its own blocks from others'
+    let mut walk_of = vec![None; blocks.len()];
+    let mut fates = vec![None; blocks.len()];
+    for first in 0..blocks.len() {
+        let mut walk = Vec::new();
+        let mut block = first;
+        let fate = loop {
+            if let Some(fate) = fates[block] {
+                break fate;
+            }
+            if walk_of[block] == Some(first) {
+                break Fate::Loops(block);
+            }
+            walk_of[block] = Some(first);
+            walk.push(block);
+            match blocks[block].successor {
+                Successor::Block(next) => block = next,
+                Successor::End => break Fate::Terminates,
+                Successor::OutOfRange(target) => break Fate::LeavesProgram(target),
+            }
+        };
+        for block in walk {
+            fates[block] = Some(fate);
+        }
+    }
+    fates
+        .into_iter()
+        .map(|fate| fate.expect("every block was walked"))
+        .collect()
+}
@@ -0,0 +1,173 @@
+use crate::isa::Instr;
+use crate::trace::TraceEntry;
+use std::convert::TryFrom;
+use util::{Error, Result};
+
+/// The CPU's registers. The program counter is signed because a jump can take it before the
+/// start of the program, which is an error only once the CPU tries to execute from there.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Registers {
+    pub acc: i64,
+    pub pc: i64,
+}
+
+/// How a run of a program ended.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Outcome {
+    /// The program ran off its end, leaving `acc` in the accumulator.
+    Terminated { acc: i64 },
+    /// The instruction at `pc` was about to run a second time with `acc` in the accumulator. Jumps
+    /// don't depend on the accumulator, so the program would repeat itself forever from there.
+    InfiniteLoop { pc: i64, acc: i64 },
+    /// The program counter left the program other than by running off its end.
+    OutOfBounds { pc: i64 },
+    /// The step limit was reached first.
+    StepLimit,
+}
+
+pub struct Cpu {
+    program: Vec<Instr>,
+    registers: Registers,
+    /// How many instructions have been executed.
+    steps: u64,
+    /// Every instruction executed so far, if tracing is on.
+    trace: Option<Vec<TraceEntry>>,
+}
+
+impl Cpu {
+    pub fn new(program: Vec<Instr>) -> Self {
+        Cpu {
+            program,
+            registers: Registers::default(),
+            steps: 0,
+            trace: None,
+        }
+    }
+
+    /// Records a trace of every instruction executed from here on.
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(Vec::new());
+        self
+    }
+
+    /// The instructions executed since tracing was turned on, if it was.
+    pub fn trace(&self) -> Option<&[TraceEntry]> {
+        self.trace.as_deref()
+    }
+
+    pub fn program(&self) -> &[Instr] {
+        &self.program
+    }
+
+    pub fn registers(&self) -> Registers {
+        self.registers
+    }
+
+    pub fn registers_mut(&mut self) -> &mut Registers {
+        &mut self.registers
+    }
+
+    pub fn acc(&self) -> i64 {
+        self.registers.acc
+    }
+
+    pub fn pc(&self) -> i64 {
+        self.registers.pc
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// The instruction the program counter points at, if it points into the program.
+    pub fn current(&self) -> Option<Instr> {
+        usize::try_from(self.registers.pc)
+            .ok()
+            .and_then(|pc| self.program.get(pc))
+            .copied()
+    }
+
+    /// Whether the program has terminated by running off its end, onto the instruction just past
+    /// the last one.
+    pub fn is_terminated(&self) -> bool {
+        usize::try_from(self.registers.pc) == Ok(self.program.len())
+    }
+
+    /// Executes the current instruction, returning the program counter afterwards, or an error if
+    /// the program counter doesn't point at an instruction.
+    pub fn step(&mut self) -> Result<i64> {
+        let instr = self.current().ok_or_else(|| {
+            Error::invalid_input(format!(
+                "Program counter {} is outside of the program",
+                self.registers.pc
+            ))
+        })?;
+        self.execute(instr);
+        Ok(self.registers.pc)
+    }
+
+    /// Steps until the program terminates or `stop` returns true, which it's asked before each
+    /// instruction is executed. Returns an error if the program counter leaves the program any
+    /// other way.
+    pub fn run_until<F>(&mut self, mut stop: F) -> Result<()>
+    where
+        F: FnMut(&Cpu) -> bool,
+    {
+        while !self.is_terminated() && !stop(self) {
+            self.step()?;
+        }
+        Ok(())
+    }
+
+    /// Runs the program until it terminates, loops or leaves the program.
+    pub fn run(&mut self) -> Outcome {
+        self.run_with_limit(u64::MAX)
+    }
+
+    /// Runs the program like [`Cpu::run`], executing at most `max_steps` instructions.
+    pub fn run_with_limit(&mut self, max_steps: u64) -> Outcome {
+        let mut executed = vec![false; self.program.len()];
+        for steps in 0.. {
+            if self.is_terminated() {
+                return Outcome::Terminated {
+                    acc: self.registers.acc,
+                };
+            }
+            let pc = match usize::try_from(self.registers.pc) {
+                Ok(pc) if pc < self.program.len() => pc,
+                _ => {
+                    return Outcome::OutOfBounds {
+                        pc: self.registers.pc,
+                    }
+                }
+            };
+            if std::mem::replace(&mut executed[pc], true) {
+                return Outcome::InfiniteLoop {
+                    pc: self.registers.pc,
+                    acc: self.registers.acc,
+                };
+            }
+            if steps == max_steps {
+                break;
+            }
+            self.execute(self.program[pc]);
+        }
+        Outcome::StepLimit
+    }
+
+    /// Executes `instr` as the instruction at the program counter.
+    fn execute(&mut self, instr: Instr) {
+        let before = self.registers;
+        (instr.op.opcode().execute)(&mut self.registers, instr.arg);
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEntry {
+                step: self.steps,
+                pc: before.pc,
+                instr,
+                acc_before: before.acc,
+                acc_after: self.registers.acc,
+            });
+        }
+        self.steps += 1;
+    }
+}
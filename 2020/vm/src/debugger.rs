@@ -0,0 +1,249 @@
+//! An interactive debugger for console programs, with breakpoints, stepping in both directions and
+//! a count of how often each instruction has run.
+//!
+//! The debugger is driven by [`Command`]s, each of which produces the text to show in reply, so it
+//! can sit behind any line-based front end.
+
+use crate::cpu::{Cpu, Registers};
+use crate::isa::Instr;
+use std::collections::{BTreeSet, HashSet};
+use std::convert::TryFrom;
+use std::str::FromStr;
+use util::{Error, Result};
+
+pub const HELP: &str = "\
+break <pc>     stop before running the instruction at <pc> (b)
+delete <pc>    remove the breakpoint at <pc> (d)
+step [n]       run the next n instructions, 1 by default (s)
+back [n]       undo the last n instructions, 1 by default (rs)
+continue       run until a breakpoint, the end of the program, or an instruction repeats (c)
+registers      show the registers and the next instruction (r)
+heat           show how often each instruction has run (h)
+help           show this help
+quit           leave the debugger (q)";
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Command {
+    Break(usize),
+    Delete(usize),
+    Step(usize),
+    Back(usize),
+    Continue,
+    Registers,
+    Heat,
+    Help,
+    Quit,
+}
+
+impl FromStr for Command {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<Command> {
+        let mut words = line.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let arg = words.next();
+        if let Some(extra) = words.next() {
+            return Err(Error::usage(format!("unexpected `{}`", extra)));
+        }
+        let number = |default: Option<usize>| {
+            arg.map(|arg| {
+                arg.parse::<usize>()
+                    .map_err(|_| Error::usage(format!("`{}` isn't a number", arg)))
+            })
+            .or_else(|| default.map(Ok))
+            .unwrap_or_else(|| Err(Error::usage(format!("`{}` expects a number", name))))
+        };
+        let no_arg = |command: Command| match arg {
+            Some(arg) => Err(Error::usage(format!("unexpected `{}`", arg))),
+            None => Ok(command),
+        };
+        match name {
+            "break" | "b" => number(None).map(Command::Break),
+            "delete" | "d" => number(None).map(Command::Delete),
+            "step" | "s" => number(Some(1)).map(Command::Step),
+            "back" | "rs" => number(Some(1)).map(Command::Back),
+            "continue" | "c" => no_arg(Command::Continue),
+            "registers" | "r" => no_arg(Command::Registers),
+            "heat" | "h" => no_arg(Command::Heat),
+            "help" => no_arg(Command::Help),
+            "quit" | "q" => no_arg(Command::Quit),
+            _ => Err(Error::usage(format!(
+                "unknown command `{}`, try `help`",
+                name
+            ))),
+        }
+    }
+}
+
+pub struct Debugger {
+    cpu: Cpu,
+    breakpoints: BTreeSet<usize>,
+    /// The registers before each instruction run so far, most recent last.
+    history: Vec<Registers>,
+    /// How many times each instruction has run.
+    hits: Vec<u64>,
+}
+
+impl Debugger {
+    pub fn new(program: Vec<Instr>) -> Self {
+        Debugger {
+            hits: vec![0; program.len()],
+            cpu: Cpu::new(program),
+            breakpoints: BTreeSet::new(),
+            history: Vec::new(),
+        }
+    }
+
+    pub fn cpu(&self) -> &Cpu {
+        &self.cpu
+    }
+
+    pub fn breakpoints(&self) -> &BTreeSet<usize> {
+        &self.breakpoints
+    }
+
+    pub fn hits(&self) -> &[u64] {
+        &self.hits
+    }
+
+    /// Carries out a command, returning what to show in reply. Quitting is left to the caller.
+    pub fn execute(&mut self, command: Command) -> String {
+        match command {
+            Command::Break(pc) if pc >= self.cpu.program().len() => {
+                format!("pc {} is outside of the program", pc)
+            }
+            Command::Break(pc) => {
+                self.breakpoints.insert(pc);
+                format!("breakpoint at pc {}: {}", pc, self.cpu.program()[pc])
+            }
+            Command::Delete(pc) => {
+                if self.breakpoints.remove(&pc) {
+                    format!("deleted the breakpoint at pc {}", pc)
+                } else {
+                    format!("there's no breakpoint at pc {}", pc)
+                }
+            }
+            Command::Step(count) => {
+                for _ in 0..count {
+                    if let Err(stopped) = self.step() {
+                        return format!("{}\n{}", stopped, self.registers());
+                    }
+                }
+                self.registers()
+            }
+            Command::Back(count) => {
+                let undone = (0..count).take_while(|_| self.back()).count();
+                if undone < count {
+                    format!("at the start of the program\n{}", self.registers())
+                } else {
+                    self.registers()
+                }
+            }
+            Command::Continue => format!("{}\n{}", self.resume(), self.registers()),
+            Command::Registers => self.registers(),
+            Command::Heat => self.heat_map(),
+            Command::Help => HELP.to_string(),
+            Command::Quit => String::new(),
+        }
+    }
+
+    /// Runs the next instruction, or explains why there isn't one to run.
+    pub fn step(&mut self) -> std::result::Result<(), String> {
+        if self.cpu.is_terminated() {
+            return Err(format!(
+                "the program has terminated with acc {}",
+                self.cpu.acc()
+            ));
+        }
+        let registers = self.cpu.registers();
+        self.cpu.step().map_err(|err| err.to_string())?;
+        self.history.push(registers);
+        self.hits[registers.pc as usize] += 1;
+        Ok(())
+    }
+
+    /// Undoes the last instruction run, returning whether there was one.
+    pub fn back(&mut self) -> bool {
+        match self.history.pop() {
+            Some(registers) => {
+                self.hits[registers.pc as usize] -= 1;
+                *self.cpu.registers_mut() = registers;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Runs until the next breakpoint, the end of the program, or an instruction that has already
+    /// run since resuming, which means the program loops forever. Returns why it stopped.
+    fn resume(&mut self) -> String {
+        let mut executed = HashSet::new();
+        executed.insert(self.cpu.pc());
+        loop {
+            if let Err(stopped) = self.step() {
+                return stopped;
+            }
+            let pc = self.cpu.pc();
+            if self.cpu.is_terminated() {
+                return format!("the program terminated with acc {}", self.cpu.acc());
+            }
+            if let Some(pc) = usize::try_from(pc)
+                .ok()
+                .filter(|pc| self.breakpoints.contains(pc))
+            {
+                return format!("stopped at the breakpoint at pc {}", pc);
+            }
+            if !executed.insert(pc) {
+                return format!(
+                    "pc {} is about to run again, so the program loops forever",
+                    pc
+                );
+            }
+        }
+    }
+
+    fn registers(&self) -> String {
+        let next = match self.cpu.current() {
+            Some(instr) => instr.to_string(),
+            None if self.cpu.is_terminated() => "end of program".to_string(),
+            None => "outside of the program".to_string(),
+        };
+        format!(
+            "acc {}  pc {}  steps {}  next: {}",
+            self.cpu.acc(),
+            self.cpu.pc(),
+            self.history.len(),
+            next
+        )
+    }
+
+    /// Lists the program with how many times each instruction has run, marking the next
+    /// instruction with `>` and breakpoints with `*`.
+    fn heat_map(&self) -> String {
+        let hottest = self.hits.iter().copied().max().unwrap_or_default().max(1);
+        let mut map = String::new();
+        for (pc, (instr, &hits)) in self.cpu.program().iter().zip(&self.hits).enumerate() {
+            let next = if self.cpu.pc() == pc as i64 { '>' } else { ' ' };
+            let breakpoint = if self.breakpoints.contains(&pc) {
+                '*'
+            } else {
+                ' '
+            };
+            // A bar of up to 20 characters, so hot spots stand out
+            let bar = "#".repeat((hits * 20).div_ceil(hottest) as usize);
+            let line = format!(
+                "{}{}{:>5}  {:<10} {:>6} {}",
+                next,
+                breakpoint,
+                pc,
+                instr.to_string(),
+                hits,
+                bar
+            );
+            map.push_str(line.trim_end());
+            map.push('\n');
+        }
+        map.pop();
+        map
+    }
+}
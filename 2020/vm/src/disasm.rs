@@ -0,0 +1,48 @@
+//! Writes programs back out as text the [assembler](crate::asm) reads, so patched programs can be
+//! saved and run again.
+
+use crate::isa::{Instr, Op};
+use std::convert::TryFrom;
+use std::fmt::Write;
+
+/// What to add to each instruction beyond its canonical form. Anything added is either a label or
+/// a comment, so the output always assembles back to the same program.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Options {
+    /// Labels each instruction with its position, as in `12: jmp -3`.
+    pub labels: bool,
+    /// Adds a comment to each jump saying where it goes, as in `jmp -3  # -> 9`.
+    pub targets: bool,
+}
+
+/// Writes a program in its canonical form, an instruction per line, the way the puzzle input is
+/// written.
+pub fn disassemble(program: &[Instr]) -> String {
+    disassemble_with(program, Options::default())
+}
+
+pub fn disassemble_with(program: &[Instr], options: Options) -> String {
+    let width = program.len().saturating_sub(1).to_string().len();
+    let mut text = String::new();
+    for (pc, &instr) in program.iter().enumerate() {
+        if options.labels {
+            let _ = write!(text, "{:>width$}: ", pc, width = width);
+        }
+        let _ = write!(text, "{}", instr);
+        if options.targets && instr.op == Op::Jmp {
+            let _ = write!(text, "  # -> {}", target(program, pc, instr));
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// Describes where the jump `instr` at `pc` goes.
+fn target(program: &[Instr], pc: usize, instr: Instr) -> String {
+    let target = instr.effect(pc as i64).pc;
+    match usize::try_from(target) {
+        Ok(target) if target < program.len() => target.to_string(),
+        Ok(target) if target == program.len() => "end".to_string(),
+        _ => format!("{} (outside of the program)", target),
+    }
+}
@@ -0,0 +1,145 @@
+//! Random programs with a known fate, for testing the loop detector and the repair solver against
+//! many more programs than the one puzzle input.
+//!
+//! Each program is built around a path: a random order in which some of its positions run,
+//! starting from 0. The instructions on the path are chosen to carry the run along it, and every
+//! other instruction is random filler, so how the program behaves is known before it's run.
+
+use crate::cpu::Outcome;
+use crate::isa::{Instr, Op};
+use crate::repair::{self, Repair};
+use util::testing::Rng;
+
+/// A generated program and what's known about it.
+#[derive(Clone, Debug)]
+pub struct Generated {
+    pub program: Vec<Instr>,
+    /// How running the program from the start ends.
+    pub outcome: Outcome,
+    /// The flip that was undone to corrupt the program, if it was corrupted.
+    pub repair: Option<Repair>,
+}
+
+/// Generates a program of `len` instructions, at least 1, that gets stuck in a loop.
+pub fn looping(rng: &mut Rng, len: usize) -> Generated {
+    let (mut program, path) = path_program(rng, len, false);
+    // Close the path into a loop back to somewhere on it
+    let last = *path.last().expect("the path is never empty");
+    let target = path[rng.below(path.len())];
+    program[last] = Instr::new(Op::Jmp, target as i64 - last as i64);
+    Generated {
+        outcome: Outcome::InfiniteLoop {
+            pc: target as i64,
+            acc: path_acc(&program, &path),
+        },
+        program,
+        repair: None,
+    }
+}
+
+/// Generates a program of `len` instructions, at least 1, that would terminate but for a single
+/// `jmp` or `nop` that's been flipped to make it loop.
+pub fn corrupted(rng: &mut Rng, len: usize) -> Generated {
+    loop {
+        let (mut program, path) = path_program(rng, len, true);
+        let acc = path_acc(&program, &path);
+        let mut step_of = vec![None; len + 1];
+        for (step, &pc) in path.iter().enumerate() {
+            step_of[pc] = Some(step);
+        }
+
+        // A flip is certain to loop if it sends the run back to somewhere it's already been: a
+        // `nop` can become a backwards `jmp`, and a `jmp` can become a `nop` if the instruction
+        // after it already ran
+        let candidates = path
+            .iter()
+            .enumerate()
+            .filter(|&(step, &pc)| match program[pc].op {
+                Op::Nop => true,
+                Op::Jmp => step_of[pc + 1].is_some_and(|next| next < step),
+                Op::Acc => false,
+            })
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            continue;
+        }
+
+        let (step, &pc) = candidates[rng.below(candidates.len())];
+        let (original, loops_to) = match program[pc].op {
+            // The nop's argument is where it jumps once flipped, so flipping it back restores it
+            Op::Nop => {
+                let loops_to = path[rng.below(step + 1)];
+                (Instr::new(Op::Nop, loops_to as i64 - pc as i64), loops_to)
+            }
+            _ => (program[pc], pc + 1),
+        };
+        let corrupted = repair::flipped(original).expect("only jmp and nop are candidates");
+        program[pc] = corrupted;
+        return Generated {
+            outcome: Outcome::InfiniteLoop {
+                pc: loops_to as i64,
+                acc: path_acc(&program, &path[..=step]),
+            },
+            repair: Some(Repair {
+                pc,
+                replacement: original,
+                acc,
+            }),
+            program,
+        };
+    }
+}
+
+/// Generates a program of `len` instructions with a path through some of them from position 0.
+/// Every instruction on the path but the last carries the run on to the next, and the last runs
+/// off the end of the program if `terminates` is set. Returns the program and its path.
+fn path_program(rng: &mut Rng, len: usize, terminates: bool) -> (Vec<Instr>, Vec<usize>) {
+    assert!(len > 0, "programs need at least one instruction");
+    let mut program = (0..len).map(|_| filler(rng, len)).collect::<Vec<_>>();
+
+    let mut order = (1..len).collect::<Vec<_>>();
+    for i in (1..order.len()).rev() {
+        order.swap(i, rng.below(i + 1));
+    }
+    let mut path = vec![0];
+    path.extend(order.into_iter().take(rng.below(len)));
+    // Runs of consecutive positions exercise falling through instead of jumping
+    if rng.chance(1, 2) {
+        path.sort_unstable();
+    }
+
+    let stops = path
+        .iter()
+        .skip(1)
+        .copied()
+        .chain(terminates.then_some(len));
+    for (&pc, next) in path.iter().zip(stops) {
+        program[pc] = if next == pc + 1 && rng.chance(3, 4) {
+            if rng.chance(1, 2) {
+                Instr::new(Op::Acc, rng.between(-100, 100))
+            } else {
+                Instr::new(Op::Nop, rng.between(-(len as i64), len as i64))
+            }
+        } else {
+            Instr::new(Op::Jmp, next as i64 - pc as i64)
+        };
+    }
+    (program, path)
+}
+
+/// A random instruction, jumping anywhere from just outside of the program to just past it.
+fn filler(rng: &mut Rng, len: usize) -> Instr {
+    let len = len as i64;
+    match rng.below(3) {
+        0 => Instr::new(Op::Acc, rng.between(-100, 100)),
+        1 => Instr::new(Op::Nop, rng.between(-len - 2, len + 2)),
+        _ => Instr::new(Op::Jmp, rng.between(-len - 2, len + 2)),
+    }
+}
+
+/// The accumulator after running each instruction on `path` once.
+fn path_acc(program: &[Instr], path: &[usize]) -> i64 {
+    path.iter()
+        .map(|&pc| program[pc].effect(pc as i64).acc)
+        .fold(0, i64::wrapping_add)
+}
@@ -0,0 +1,92 @@
+//! The console's instruction set.
+//!
+//! Every instruction is an operation and a single signed argument. What each operation does is
+//! described by its entry in [`OPCODES`], so adding one means adding a variant to [`Op`] and a row
+//! to the table; the assembler and the CPU pick it up from there.
+
+use crate::cpu::Registers;
+use std::fmt;
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Op {
+    /// Adds the argument to the accumulator.
+    Acc,
+    /// Jumps relative to itself by the argument.
+    Jmp,
+    /// Does nothing, ignoring the argument.
+    Nop,
+}
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Instr {
+    pub op: Op,
+    pub arg: i64,
+}
+
+/// An entry in the opcode table: how an operation is written, and what it does to the registers
+/// when executed with an argument.
+pub struct Opcode {
+    pub op: Op,
+    pub mnemonic: &'static str,
+    pub execute: fn(&mut Registers, i64),
+}
+
+/// Every operation, in the same order as the variants of [`Op`].
+pub const OPCODES: [Opcode; 3] = [
+    Opcode {
+        op: Op::Acc,
+        mnemonic: "acc",
+        execute: |registers, arg| {
+            registers.acc = registers.acc.wrapping_add(arg);
+            registers.pc += 1;
+        },
+    },
+    Opcode {
+        op: Op::Jmp,
+        mnemonic: "jmp",
+        execute: |registers, arg| registers.pc = registers.pc.wrapping_add(arg),
+    },
+    Opcode {
+        op: Op::Nop,
+        mnemonic: "nop",
+        execute: |registers, _| registers.pc += 1,
+    },
+];
+
+impl Op {
+    pub fn opcode(self) -> &'static Opcode {
+        &OPCODES[self as usize]
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        self.opcode().mnemonic
+    }
+
+    pub fn from_mnemonic(mnemonic: &str) -> Option<Op> {
+        OPCODES
+            .iter()
+            .find(|opcode| opcode.mnemonic == mnemonic)
+            .map(|opcode| opcode.op)
+    }
+}
+
+impl Instr {
+    pub fn new(op: Op, arg: i64) -> Self {
+        Instr { op, arg }
+    }
+
+    /// The registers after executing the instruction at `pc` with an empty accumulator, which is
+    /// where it continues and how much it changes the accumulator by.
+    pub fn effect(self, pc: i64) -> Registers {
+        let mut registers = Registers { acc: 0, pc };
+        (self.op.opcode().execute)(&mut registers, self.arg);
+        registers
+    }
+}
+
+/// Writes the instruction the way the assembler reads it, as in `jmp -3`.
+impl fmt::Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:+}", self.op.mnemonic(), self.arg)
+    }
+}
@@ -0,0 +1,18 @@
+//! The handheld game console from day 8: its instruction set, an assembler for its programs, and a
+//! CPU to run them on.
+
+pub mod asm;
+pub mod cfg;
+pub mod cpu;
+pub mod debugger;
+pub mod disasm;
+#[cfg(feature = "testing")]
+pub mod generate;
+pub mod isa;
+pub mod repair;
+pub mod trace;
+
+pub use asm::assemble;
+pub use cpu::{Cpu, Outcome, Registers};
+pub use disasm::disassemble;
+pub use isa::{Instr, Op, Opcode, OPCODES};
@@ -0,0 +1,155 @@
+//! Repairs programs that are corrupted by a single `jmp` that should be a `nop`, or vice versa.
+//!
+//! Rather than trying every flip and rerunning the program, this works out once, by walking the
+//! reverse control-flow graph back from the end of the program, which instructions already run to
+//! termination. A flip then fixes the program exactly when the instruction is one the program
+//! executes and its flipped successor is among those, so a single forward run finds every repair.
+//!
+//! The one catch is a program that already terminates, where the flipped successor's way to the
+//! end can run through the flipped instruction itself, which then sends it round again. Those
+//! successors are exactly the ones in the flipped instruction's subtree of the reverse graph,
+//! which is a tree since every instruction has one successor, so the walk also records when it
+//! enters and leaves each subtree to tell them apart.
+
+use crate::isa::{Instr, Op};
+use std::convert::TryFrom;
+
+/// A single flipped instruction that makes a program terminate.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Repair {
+    /// Where the flipped instruction is.
+    pub pc: usize,
+    /// What it's replaced with.
+    pub replacement: Instr,
+    /// The accumulator once the repaired program terminates.
+    pub acc: i64,
+}
+
+impl Repair {
+    /// A copy of `program` with the repair made.
+    pub fn apply(&self, program: &[Instr]) -> Vec<Instr> {
+        let mut repaired = program.to_vec();
+        repaired[self.pc] = self.replacement;
+        repaired
+    }
+}
+
+/// The instruction with its `jmp` or `nop` swapped for the other, if it's either.
+pub fn flipped(instr: Instr) -> Option<Instr> {
+    let op = match instr.op {
+        Op::Jmp => Op::Nop,
+        Op::Nop => Op::Jmp,
+        Op::Acc => return None,
+    };
+    Some(Instr::new(op, instr.arg))
+}
+
+/// The first repair the program would reach when run, if there is one.
+pub fn repair(program: &[Instr]) -> Option<Repair> {
+    repairs(program).into_iter().next()
+}
+
+/// Every single flip that makes the program terminate, in the order the program reaches the
+/// flipped instructions. Only instructions the unrepaired program executes are considered, since
+/// flipping any other leaves the run unchanged.
+pub fn repairs(program: &[Instr]) -> Vec<Repair> {
+    let to_end = PathsToEnd::new(program);
+    let mut repairs = Vec::new();
+    let mut executed = vec![false; program.len()];
+    let (mut pc, mut acc) = (0, 0_i64);
+    while pc < program.len() && !std::mem::replace(&mut executed[pc], true) {
+        let instr = program[pc];
+        if let Some(replacement) = flipped(instr) {
+            let remaining = successor(program.len(), pc, replacement)
+                .filter(|&next| !to_end.passes_through(next, pc))
+                .and_then(|next| to_end.acc[next]);
+            if let Some(remaining) = remaining {
+                repairs.push(Repair {
+                    pc,
+                    replacement,
+                    acc: acc.wrapping_add(remaining),
+                });
+            }
+        }
+        acc = acc.wrapping_add(accumulated(instr));
+        match successor(program.len(), pc, instr) {
+            Some(next) => pc = next,
+            None => break,
+        }
+    }
+    repairs
+}
+
+/// How running from each position from the start of the program up to and including its end
+/// reaches the end, if it does.
+struct PathsToEnd {
+    /// How much the accumulator changes on the way to the end, or `None` if running from there
+    /// never reaches the end.
+    acc: Vec<Option<i64>>,
+    /// When the walk back from the end entered and left each position's subtree, which holds the
+    /// positions whose way to the end runs through it.
+    entered: Vec<usize>,
+    left: Vec<usize>,
+}
+
+impl PathsToEnd {
+    fn new(program: &[Instr]) -> Self {
+        let end = program.len();
+        let mut predecessors = vec![Vec::new(); end + 1];
+        for (pc, &instr) in program.iter().enumerate() {
+            if let Some(next) = successor(end, pc, instr) {
+                predecessors[next].push(pc);
+            }
+        }
+
+        // A depth-first walk, so each position's successor is settled before it and its subtree
+        // is walked in one go
+        let mut paths = PathsToEnd {
+            acc: vec![None; end + 1],
+            entered: vec![0; end + 1],
+            left: vec![0; end + 1],
+        };
+        paths.acc[end] = Some(0);
+        let mut stack = vec![(end, 0)];
+        let mut time = 0;
+        while let Some((next, child)) = stack.pop() {
+            if child == 0 {
+                paths.entered[next] = time;
+                time += 1;
+            }
+            match predecessors[next].get(child) {
+                Some(&pc) => {
+                    let remaining = paths.acc[next].unwrap_or_default();
+                    paths.acc[pc] = Some(accumulated(program[pc]).wrapping_add(remaining));
+                    stack.push((next, child + 1));
+                    stack.push((pc, 0));
+                }
+                None => {
+                    paths.left[next] = time;
+                    time += 1;
+                }
+            }
+        }
+        paths
+    }
+
+    /// Whether running from `from` reaches the end by way of `through`.
+    fn passes_through(&self, from: usize, through: usize) -> bool {
+        self.acc[from].is_some()
+            && self.acc[through].is_some()
+            && self.entered[through] <= self.entered[from]
+            && self.left[from] <= self.left[through]
+    }
+}
+
+/// Where the program continues after `instr` at `pc`, if that's within the program or its end.
+fn successor(end: usize, pc: usize, instr: Instr) -> Option<usize> {
+    usize::try_from(instr.effect(pc as i64).pc)
+        .ok()
+        .filter(|&next| next <= end)
+}
+
+/// How much executing `instr` changes the accumulator.
+fn accumulated(instr: Instr) -> i64 {
+    instr.effect(0).acc
+}
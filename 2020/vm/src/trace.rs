@@ -0,0 +1,90 @@
+//! Traces of the instructions a [`Cpu`](crate::Cpu) executed, and exporting them as CSV or JSON
+//! lines so the runs of two programs can be compared with ordinary diff tools.
+
+use crate::isa::Instr;
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+use util::{Error, Result};
+
+/// An executed instruction, and the accumulator either side of it.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TraceEntry {
+    /// How many instructions ran before this one.
+    pub step: u64,
+    pub pc: i64,
+    pub instr: Instr,
+    pub acc_before: i64,
+    pub acc_after: i64,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// A header line followed by a line per entry.
+    Csv,
+    /// A JSON object per line, per entry.
+    JsonLines,
+}
+
+impl Format {
+    /// Picks the format matching a file's extension: `csv`, or `jsonl` or `json` for JSON lines.
+    pub fn from_path(path: &Path) -> Result<Format> {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .ok_or_else(|| {
+                Error::usage(format!(
+                    "can't tell the trace format of {} without an extension",
+                    path.display()
+                ))
+            })?
+            .parse()
+    }
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Format> {
+        match name {
+            "csv" => Ok(Format::Csv),
+            "jsonl" | "json" => Ok(Format::JsonLines),
+            _ => Err(Error::usage(format!(
+                "unknown trace format `{}`, expected `csv` or `jsonl`",
+                name
+            ))),
+        }
+    }
+}
+
+/// Writes a trace in `format`, an entry per line.
+pub fn export<W: Write>(trace: &[TraceEntry], format: Format, mut out: W) -> io::Result<()> {
+    // Every field is a number or a mnemonic, so neither format needs any quoting or escaping
+    if format == Format::Csv {
+        writeln!(out, "step,pc,op,arg,acc_before,acc_after")?;
+    }
+    for entry in trace {
+        match format {
+            Format::Csv => writeln!(
+                out,
+                "{},{},{},{},{},{}",
+                entry.step,
+                entry.pc,
+                entry.instr.op.mnemonic(),
+                entry.instr.arg,
+                entry.acc_before,
+                entry.acc_after
+            )?,
+            Format::JsonLines => writeln!(
+                out,
+                r#"{{"step":{},"pc":{},"op":"{}","arg":{},"acc_before":{},"acc_after":{}}}"#,
+                entry.step,
+                entry.pc,
+                entry.instr.op.mnemonic(),
+                entry.instr.arg,
+                entry.acc_before,
+                entry.acc_after
+            )?,
+        }
+    }
+    out.flush()
+}
@@ -0,0 +1,45 @@
+use util::Error;
+use vm::{assemble, Instr, Op, OPCODES};
+
+#[test]
+fn assembles_each_line_into_an_instruction() {
+    let program = assemble("nop +0\nacc +1\n\njmp -4\n").unwrap();
+    assert_eq!(
+        program,
+        vec![
+            Instr::new(Op::Nop, 0),
+            Instr::new(Op::Acc, 1),
+            Instr::new(Op::Jmp, -4),
+        ]
+    );
+}
+
+#[test]
+fn reports_where_an_instruction_is_invalid() {
+    let located = |source| match assemble(source).unwrap_err() {
+        Error::Parse {
+            line,
+            column,
+            message,
+            ..
+        } => (line, column, message),
+        err => panic!("unexpected error: {}", err),
+    };
+    assert_eq!(
+        located("nop +0\nhlt +1\n"),
+        (2, 1, "unknown opcode `hlt`".to_string())
+    );
+    let (line, column, _) = located("acc +1\njmp\n");
+    assert_eq!((line, column), (2, 4));
+    let (line, column, _) = located("acc +1\n\nacc x1\n");
+    assert_eq!((line, column), (3, 5));
+    assert_eq!(located("acc +1 +2"), (1, 8, "unexpected `+2`".to_string()));
+}
+
+#[test]
+fn opcode_table_matches_the_operations() {
+    for (i, opcode) in OPCODES.iter().enumerate() {
+        assert_eq!(opcode.op as usize, i);
+        assert_eq!(Op::from_mnemonic(opcode.mnemonic), Some(opcode.op));
+    }
+}
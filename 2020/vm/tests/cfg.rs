@@ -0,0 +1,84 @@
+mod common;
+
+use common::LOOPING;
+use vm::assemble;
+use vm::cfg::{Block, Cfg, Fate, Successor};
+
+#[test]
+fn splits_the_program_into_basic_blocks() {
+    let program = assemble(LOOPING).unwrap();
+    let cfg = Cfg::new(&program);
+    let blocks = cfg
+        .blocks()
+        .iter()
+        .map(|block| (block.start, block.end, block.successor))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        blocks,
+        vec![
+            (0, 1, Successor::Block(1)),
+            (1, 3, Successor::Block(4)),
+            (3, 5, Successor::Block(1)),
+            (5, 6, Successor::Block(4)),
+            (6, 8, Successor::Block(2)),
+            (8, 9, Successor::End),
+        ]
+    );
+    assert_eq!(cfg.block_of(7), 4);
+}
+
+#[test]
+fn explains_where_the_program_loops() {
+    let program = assemble(LOOPING).unwrap();
+    let cfg = Cfg::new(&program);
+    let path = cfg.entry_path().unwrap();
+    assert_eq!(path.blocks, vec![0, 1, 4, 2]);
+    assert_eq!(path.fate, Fate::Loops(1));
+    // The same accumulator the looping program has just before it repeats an instruction
+    assert_eq!(path.acc, 5);
+    assert_eq!(cfg.unreachable(), vec![5, 8]);
+    assert_eq!(cfg.never_terminates(), (0..8).collect::<Vec<_>>());
+}
+
+#[test]
+fn flags_jumps_outside_of_the_program() {
+    let program = assemble("acc +1\njmp +2\njmp -3\nacc +1\njmp +1\n").unwrap();
+    let cfg = Cfg::new(&program);
+    assert_eq!(cfg.out_of_range(), vec![(2, -1)]);
+    assert_eq!(cfg.entry_path().unwrap().fate, Fate::Terminates);
+    assert_eq!(cfg.entry_path().unwrap().acc, 2);
+    assert_eq!(cfg.unreachable(), vec![2]);
+    assert_eq!(cfg.never_terminates(), vec![2]);
+    assert_eq!(cfg.fates()[cfg.block_of(2)], Fate::LeavesProgram(-1));
+}
+
+#[test]
+fn handles_empty_programs_and_jumps_to_themselves() {
+    let cfg = Cfg::new(&[]);
+    assert!(cfg.blocks().is_empty());
+    assert_eq!(cfg.entry_path(), None);
+
+    let program = assemble("jmp +0\n").unwrap();
+    let cfg = Cfg::new(&program);
+    assert_eq!(
+        cfg.blocks(),
+        &[Block {
+            start: 0,
+            end: 1,
+            successor: Successor::Block(0),
+        }]
+    );
+    assert_eq!(cfg.fates(), &[Fate::Loops(0)]);
+}
+
+#[test]
+fn exports_dot() {
+    let program = assemble(LOOPING).unwrap();
+    let dot = Cfg::new(&program).to_dot();
+    assert!(dot.starts_with("digraph program {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert!(dot.contains(r#"b1 [label="1: acc +1\l2: jmp +4\l", color=red];"#));
+    assert!(dot.contains("b2 -> b1 [style=bold, color=red];"));
+    assert!(dot.contains("b5 -> end;"));
+    assert!(dot.contains(r#"b5 [label="8: acc +6\l", style=dashed"#));
+}
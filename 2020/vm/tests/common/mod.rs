@@ -0,0 +1,69 @@
+//! The puzzle's example program, and oracles the vm is checked against: an interpreter written
+//! independently of the opcode table, and repairs found by trying every flip.
+
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use vm::repair::{self, Repair};
+use vm::{Instr, Op, Outcome, Registers};
+
+/// The example program from the puzzle, which loops until its `jmp -4` is flipped to a `nop`.
+pub const LOOPING: &str =
+    "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n";
+
+/// Runs a program an instruction at a time, remembering every position it has run. Returns the
+/// registers before each instruction and once the run ends, which is when it terminates, leaves
+/// the program, or is about to run an instruction a second time.
+pub fn oracle_trace(program: &[Instr]) -> (Vec<Registers>, Outcome) {
+    let mut registers = Registers::default();
+    let mut trace = vec![registers];
+    let mut executed = HashSet::new();
+    loop {
+        let Registers { acc, pc } = registers;
+        let instr = match usize::try_from(pc) {
+            Ok(at) if at == program.len() => return (trace, Outcome::Terminated { acc }),
+            Ok(at) if at < program.len() => program[at],
+            _ => return (trace, Outcome::OutOfBounds { pc }),
+        };
+        if !executed.insert(pc) {
+            return (trace, Outcome::InfiniteLoop { pc, acc });
+        }
+        registers = match instr.op {
+            Op::Acc => Registers {
+                acc: acc.wrapping_add(instr.arg),
+                pc: pc + 1,
+            },
+            Op::Jmp => Registers {
+                acc,
+                pc: pc.wrapping_add(instr.arg),
+            },
+            Op::Nop => Registers { acc, pc: pc + 1 },
+        };
+        trace.push(registers);
+    }
+}
+
+/// How running a program ends, according to the oracle.
+pub fn oracle_run(program: &[Instr]) -> Outcome {
+    oracle_trace(program).1
+}
+
+/// Every repair found by flipping each instruction in turn and running the result.
+pub fn brute_force_repairs(program: &[Instr]) -> Vec<Repair> {
+    (0..program.len())
+        .filter_map(|pc| {
+            let replacement = repair::flipped(program[pc])?;
+            let mut repaired = program.to_vec();
+            repaired[pc] = replacement;
+            match oracle_run(&repaired) {
+                Outcome::Terminated { acc } => Some(Repair {
+                    pc,
+                    replacement,
+                    acc,
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use common::LOOPING;
+use vm::{assemble, Cpu, Outcome, Registers};
+
+#[test]
+fn steps_through_instructions() {
+    let mut cpu = Cpu::new(assemble(LOOPING).unwrap());
+    assert_eq!(cpu.step().unwrap(), 1);
+    assert_eq!(cpu.step().unwrap(), 2);
+    assert_eq!(cpu.registers(), Registers { acc: 1, pc: 2 });
+    assert_eq!(cpu.step().unwrap(), 6);
+    assert_eq!(cpu.steps(), 3);
+}
+
+#[test]
+fn runs_until_told_to_stop() {
+    let mut cpu = Cpu::new(assemble(LOOPING).unwrap());
+    let mut seen = std::collections::HashSet::new();
+    cpu.run_until(|cpu| !seen.insert(cpu.pc())).unwrap();
+    assert_eq!(cpu.acc(), 5);
+    assert_eq!(cpu.pc(), 1);
+    assert!(!cpu.is_terminated());
+}
+
+#[test]
+fn runs_until_the_program_terminates() {
+    let mut cpu = Cpu::new(assemble("acc +2\njmp +2\nacc +5\nacc -1\n").unwrap());
+    cpu.run_until(|_| false).unwrap();
+    assert!(cpu.is_terminated());
+    assert_eq!(cpu.acc(), 1);
+}
+
+#[test]
+fn fails_when_jumping_outside_of_the_program() {
+    let mut cpu = Cpu::new(assemble("nop +0\njmp -5\n").unwrap());
+    assert!(cpu.run_until(|_| false).is_err());
+    assert_eq!(cpu.pc(), -4);
+
+    let mut cpu = Cpu::new(assemble("jmp +7\n").unwrap());
+    assert!(cpu.run_until(|_| false).is_err());
+}
+
+#[test]
+fn registers_can_be_set_directly() {
+    let mut cpu = Cpu::new(assemble("acc +1\nacc +2\n").unwrap());
+    cpu.registers_mut().pc = 1;
+    cpu.run_until(|_| false).unwrap();
+    assert_eq!(cpu.acc(), 2);
+}
+
+#[test]
+fn run_reports_how_the_program_ended() {
+    let run = |source| Cpu::new(assemble(source).unwrap()).run();
+    assert_eq!(run(LOOPING), Outcome::InfiniteLoop { pc: 1, acc: 5 });
+    assert_eq!(
+        run("acc +2\njmp +2\nacc +5\nacc -1\n"),
+        Outcome::Terminated { acc: 1 }
+    );
+    assert_eq!(run("nop +0\njmp -5\n"), Outcome::OutOfBounds { pc: -4 });
+    assert_eq!(run("acc +1\njmp +3\n"), Outcome::OutOfBounds { pc: 4 });
+    assert_eq!(run("jmp +0\n"), Outcome::InfiniteLoop { pc: 0, acc: 0 });
+}
+
+#[test]
+fn run_stops_at_the_step_limit() {
+    let mut cpu = Cpu::new(assemble(LOOPING).unwrap());
+    assert_eq!(cpu.run_with_limit(3), Outcome::StepLimit);
+    assert_eq!(cpu.steps(), 3);
+
+    let mut cpu = Cpu::new(assemble("acc +1\nacc +1\n").unwrap());
+    assert_eq!(cpu.run_with_limit(2), Outcome::Terminated { acc: 2 });
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::LOOPING;
+use vm::assemble;
+use vm::debugger::{Command, Debugger};
+
+fn debugger(source: &str) -> Debugger {
+    Debugger::new(assemble(source).unwrap())
+}
+
+#[test]
+fn parses_commands_and_their_abbreviations() {
+    assert_eq!("b 4".parse::<Command>().unwrap(), Command::Break(4));
+    assert_eq!("step".parse::<Command>().unwrap(), Command::Step(1));
+    assert_eq!("s 10".parse::<Command>().unwrap(), Command::Step(10));
+    assert_eq!("rs".parse::<Command>().unwrap(), Command::Back(1));
+    assert_eq!("continue".parse::<Command>().unwrap(), Command::Continue);
+    assert!("break".parse::<Command>().is_err());
+    assert!("step x".parse::<Command>().is_err());
+    assert!("heat 3".parse::<Command>().is_err());
+    assert!("jump 3".parse::<Command>().is_err());
+}
+
+#[test]
+fn continues_to_breakpoints() {
+    let mut debugger = debugger(LOOPING);
+    debugger.execute(Command::Break(4));
+    let reply = debugger.execute(Command::Continue);
+    assert!(
+        reply.starts_with("stopped at the breakpoint at pc 4"),
+        "{}",
+        reply
+    );
+    assert_eq!((debugger.cpu().pc(), debugger.cpu().acc()), (4, 5));
+
+    // Continuing again goes round the loop once more
+    debugger.execute(Command::Continue);
+    assert_eq!((debugger.cpu().pc(), debugger.cpu().acc()), (4, 10));
+}
+
+#[test]
+fn continue_stops_when_the_program_loops_or_terminates() {
+    let mut debugger = debugger(LOOPING);
+    let reply = debugger.execute(Command::Continue);
+    assert!(reply.contains("loops forever"), "{}", reply);
+    assert_eq!((debugger.cpu().pc(), debugger.cpu().acc()), (1, 5));
+
+    let mut debugger = self::debugger("acc +2\njmp +2\nacc +5\nacc -1\n");
+    let reply = debugger.execute(Command::Continue);
+    assert!(
+        reply.starts_with("the program terminated with acc 1"),
+        "{}",
+        reply
+    );
+    assert!(debugger.cpu().is_terminated());
+}
+
+#[test]
+fn steps_backwards_through_history() {
+    let mut debugger = debugger(LOOPING);
+    debugger.execute(Command::Step(6));
+    assert_eq!((debugger.cpu().pc(), debugger.cpu().acc()), (4, 5));
+    debugger.execute(Command::Back(2));
+    assert_eq!((debugger.cpu().pc(), debugger.cpu().acc()), (7, 2));
+    assert_eq!(debugger.hits(), &[1, 1, 1, 0, 0, 0, 1, 0, 0]);
+
+    let reply = debugger.execute(Command::Back(10));
+    assert!(
+        reply.starts_with("at the start of the program"),
+        "{}",
+        reply
+    );
+    assert_eq!(debugger.cpu().registers(), Default::default());
+    assert!(debugger.hits().iter().all(|&hits| hits == 0));
+}
+
+#[test]
+fn heat_map_counts_each_instruction_run() {
+    let mut debugger = debugger(LOOPING);
+    debugger.execute(Command::Break(3));
+    debugger.execute(Command::Step(13));
+    assert_eq!(debugger.hits(), &[1, 2, 2, 2, 2, 0, 2, 2, 0]);
+
+    let heat = debugger.execute(Command::Heat);
+    let lines = heat.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 9);
+    assert!(lines[1].starts_with(">"), "{}", lines[1]);
+    assert!(lines[1].ends_with(&"#".repeat(20)), "{}", lines[1]);
+    assert!(lines[3].starts_with(" *"), "{}", lines[3]);
+    assert!(lines[5].ends_with(" 0"), "{}", lines[5]);
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::LOOPING;
+use proptest::prelude::*;
+use vm::disasm::{self, Options};
+use vm::{assemble, disassemble, Instr, Op, OPCODES};
+
+fn instr() -> impl Strategy<Value = Instr> {
+    (0..OPCODES.len(), any::<i64>()).prop_map(|(op, arg)| Instr::new(OPCODES[op].op, arg))
+}
+
+fn options() -> impl Strategy<Value = Options> {
+    (any::<bool>(), any::<bool>()).prop_map(|(labels, targets)| Options { labels, targets })
+}
+
+#[test]
+fn writes_the_canonical_form() {
+    assert_eq!(disassemble(&assemble(LOOPING).unwrap()), LOOPING);
+    assert_eq!(disassemble(&[]), "");
+}
+
+#[test]
+fn labels_instructions_and_annotates_jumps() {
+    let program = assemble(
+        "jmp +2\nacc +1\njmp -5\nnop +0\nacc +1\nacc +1\nacc +1\nacc +1\nacc +1\nacc +1\njmp +1\n",
+    )
+    .unwrap();
+    let text = disasm::disassemble_with(
+        &program,
+        Options {
+            labels: true,
+            targets: true,
+        },
+    );
+    let lines = text.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], " 0: jmp +2  # -> 2");
+    assert_eq!(lines[1], " 1: acc +1");
+    assert_eq!(lines[2], " 2: jmp -5  # -> -3 (outside of the program)");
+    assert_eq!(lines[3], " 3: nop +0");
+    assert_eq!(lines[10], "10: jmp +1  # -> end");
+}
+
+#[test]
+fn rejects_labels_that_dont_match_their_position() {
+    assert!(assemble("0: nop +0\n1: acc +1\n").is_ok());
+    assert!(assemble("0: nop +0\n2: acc +1\n").is_err());
+    assert!(assemble("0: # nothing here\n").is_err());
+}
+
+proptest! {
+    #[test]
+    fn printing_then_parsing_gives_back_the_program(
+        program in prop::collection::vec(instr(), 0..64),
+        options in options(),
+    ) {
+        let text = disasm::disassemble_with(&program, options);
+        prop_assert_eq!(assemble(&text).unwrap(), program);
+    }
+
+    #[test]
+    fn parsing_then_printing_gives_back_canonical_text(
+        program in prop::collection::vec(instr(), 0..64),
+    ) {
+        let text = disassemble(&program);
+        prop_assert_eq!(disassemble(&assemble(&text).unwrap()), text);
+    }
+
+    #[test]
+    fn parsing_is_insensitive_to_spacing_and_comments(
+        program in prop::collection::vec(instr(), 1..32),
+        padding in "[ \t]{1,3}",
+    ) {
+        let text = program
+            .iter()
+            .map(|instr| format!("{}{}{}{:+}{}# comment\n", padding, instr.op.mnemonic(), padding, instr.arg, padding))
+            .collect::<String>();
+        prop_assert_eq!(assemble(&text).unwrap(), program);
+    }
+}
+
+#[test]
+fn instructions_print_with_a_signed_argument() {
+    assert_eq!(Instr::new(Op::Nop, 0).to_string(), "nop +0");
+    assert_eq!(
+        Instr::new(Op::Jmp, i64::MIN).to_string(),
+        format!("jmp {}", i64::MIN)
+    );
+}
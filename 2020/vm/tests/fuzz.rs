@@ -0,0 +1,125 @@
+//! Differential tests of the loop detector, the repair solver and the static analysis against
+//! brute-force oracles, over thousands of generated programs.
+
+mod common;
+
+use common::{brute_force_repairs, oracle_run, oracle_trace};
+use util::testing::Rng;
+use vm::cfg::{Cfg, Fate};
+use vm::generate;
+use vm::repair;
+use vm::{assemble, disassemble, Cpu, Instr, Outcome};
+
+const PROGRAMS: u64 = 3000;
+const MAX_LEN: usize = 60;
+
+#[test]
+fn loop_detection_matches_the_oracle() {
+    let mut rng = Rng::new(8);
+    for _ in 0..PROGRAMS {
+        let len = 1 + rng.below(MAX_LEN);
+        let generated = generate::looping(&mut rng, len);
+        let program = &generated.program;
+        assert_eq!(oracle_run(program), generated.outcome, "{:?}", program);
+        assert_eq!(Cpu::new(program.clone()).run(), generated.outcome);
+
+        let path = Cfg::new(program).entry_path().unwrap();
+        assert!(matches!(path.fate, Fate::Loops(_)), "{:?}", program);
+        match generated.outcome {
+            Outcome::InfiniteLoop { acc, .. } => assert_eq!(path.acc, acc),
+            outcome => panic!("unexpected outcome {:?}", outcome),
+        }
+    }
+}
+
+#[test]
+fn repairs_match_the_oracle() {
+    let mut rng = Rng::new(2020);
+    for _ in 0..PROGRAMS {
+        let len = 1 + rng.below(MAX_LEN);
+        let generated = generate::corrupted(&mut rng, len);
+        let program = &generated.program;
+        assert_eq!(Cpu::new(program.clone()).run(), generated.outcome);
+
+        let mut repairs = repair::repairs(program);
+        let first = repairs.first().copied();
+        repairs.sort_by_key(|repair| repair.pc);
+        assert_eq!(repairs, brute_force_repairs(program), "{:?}", program);
+        let planted = generated.repair.unwrap();
+        assert!(repairs.contains(&planted), "{:?}", program);
+
+        // The first repair is the one the corrupted program reaches first
+        let first = first.unwrap();
+        assert_eq!(
+            Cpu::new(first.apply(program)).run(),
+            Outcome::Terminated { acc: first.acc }
+        );
+    }
+}
+
+#[test]
+fn runs_of_arbitrary_programs_match_the_oracle() {
+    let mut rng = Rng::new(11);
+    for _ in 0..PROGRAMS {
+        let len = rng.below(MAX_LEN);
+        let program = (0..len)
+            .map(|_| {
+                let op = vm::OPCODES[rng.below(vm::OPCODES.len())].op;
+                Instr::new(op, rng.between(-(len as i64) - 3, len as i64 + 3))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(Cpu::new(program.clone()).run(), oracle_run(&program));
+        let mut repairs = repair::repairs(&program);
+        repairs.sort_by_key(|repair| repair.pc);
+        // Flipping an instruction a run never reaches changes nothing, so a program that already
+        // terminates has those repairs too
+        let mut expected = brute_force_repairs(&program);
+        if let Outcome::Terminated { .. } = oracle_run(&program) {
+            let unreachable = Cfg::new(&program).unreachable();
+            expected.retain(|repair| !unreachable.contains(&repair.pc));
+        }
+        assert_eq!(repairs, expected, "{:?}", program);
+    }
+}
+
+/// Mangles text by replacing, inserting and deleting random characters.
+fn mutate(rng: &mut Rng, text: &str) -> String {
+    const ALPHABET: &[u8] = b"acjmnop +-0123456789:#\n\t\r";
+    let mut bytes = text.as_bytes().to_vec();
+    for _ in 0..1 + rng.below(4) {
+        let chr = ALPHABET[rng.below(ALPHABET.len())];
+        let at = rng.below(bytes.len() + 1);
+        match rng.below(3) {
+            0 if at < bytes.len() => bytes[at] = chr,
+            1 if at < bytes.len() => {
+                bytes.remove(at);
+            }
+            _ => bytes.insert(at, chr),
+        }
+    }
+    String::from_utf8(bytes).unwrap()
+}
+
+#[test]
+fn mangled_source_never_panics() {
+    let mut rng = Rng::new(1);
+    for _ in 0..PROGRAMS {
+        let len = 1 + rng.below(20);
+        let program = generate::looping(&mut rng, len).program;
+        let source = mutate(&mut rng, &disassemble(&program));
+        if let Ok(program) = assemble(&source) {
+            // Stepping follows the oracle's run until it ends, and only then fails if the run
+            // left the program
+            let (trace, outcome) = oracle_trace(&program);
+            let mut cpu = Cpu::new(program);
+            for expected in &trace[1..] {
+                cpu.step().unwrap();
+                assert_eq!(cpu.registers(), *expected, "{:?}", source);
+            }
+            match outcome {
+                Outcome::InfiniteLoop { .. } => assert!(cpu.step().is_ok()),
+                _ => assert!(cpu.step().is_err()),
+            }
+        }
+    }
+}
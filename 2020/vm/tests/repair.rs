@@ -0,0 +1,63 @@
+mod common;
+
+use common::{brute_force_repairs, LOOPING};
+use vm::repair::{self, Repair};
+use vm::{assemble, Instr, Op};
+
+#[test]
+fn repairs_the_example() {
+    let program = assemble(LOOPING).unwrap();
+    assert_eq!(
+        repair::repair(&program),
+        Some(Repair {
+            pc: 7,
+            replacement: Instr::new(Op::Nop, -4),
+            acc: 8,
+        })
+    );
+}
+
+#[test]
+fn finds_every_repair_in_the_order_they_are_reached() {
+    let program = assemble("acc +1\njmp +0\nacc +2\n").unwrap();
+    let repairs = repair::repairs(&program);
+    assert_eq!(repairs, brute_force_repairs(&program));
+    assert_eq!(
+        repairs.iter().map(|repair| repair.pc).collect::<Vec<_>>(),
+        vec![1]
+    );
+
+    let program = assemble("nop +3\njmp +0\nacc +1\nacc +5\n").unwrap();
+    let repairs = repair::repairs(&program);
+    assert_eq!(repairs, brute_force_repairs(&program));
+    assert_eq!(
+        repairs
+            .iter()
+            .map(|repair| (repair.pc, repair.acc))
+            .collect::<Vec<_>>(),
+        vec![(0, 5), (1, 6)]
+    );
+}
+
+#[test]
+fn finds_nothing_when_no_flip_helps() {
+    let program = assemble("acc +1\njmp +0\nacc +1\njmp -1\n").unwrap();
+    assert_eq!(repair::repairs(&program), vec![]);
+    assert_eq!(repair::repair(&[]), None);
+}
+
+#[test]
+fn ignores_flips_that_jump_out_of_the_program() {
+    // Falling through the first jmp +0 only reaches the second
+    let program = assemble("nop +5\njmp +0\njmp +0\n").unwrap();
+    assert_eq!(repair::repairs(&program), brute_force_repairs(&program));
+    assert_eq!(repair::repairs(&program), vec![]);
+}
+
+#[test]
+fn rejects_flips_whose_way_out_runs_back_through_them() {
+    // The program already terminates, but flipping either instruction sends it round again
+    let program = assemble("nop +0\nnop -1\n").unwrap();
+    assert_eq!(repair::repairs(&program), brute_force_repairs(&program));
+    assert_eq!(repair::repairs(&program), vec![]);
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::LOOPING;
+use std::path::Path;
+use vm::trace::{self, Format, TraceEntry};
+use vm::{assemble, Cpu, Instr, Op};
+
+#[test]
+fn records_every_executed_instruction_when_asked() {
+    let mut cpu = Cpu::new(assemble(LOOPING).unwrap());
+    cpu.run();
+    assert_eq!(cpu.trace(), None);
+
+    let mut cpu = Cpu::new(assemble(LOOPING).unwrap()).with_trace();
+    cpu.run();
+    let trace = cpu.trace().unwrap();
+    assert_eq!(trace.len() as u64, cpu.steps());
+    assert_eq!(
+        trace.iter().map(|entry| entry.pc).collect::<Vec<_>>(),
+        vec![0, 1, 2, 6, 7, 3, 4]
+    );
+    assert_eq!(
+        trace[5],
+        TraceEntry {
+            step: 5,
+            pc: 3,
+            instr: Instr::new(Op::Acc, 3),
+            acc_before: 2,
+            acc_after: 5,
+        }
+    );
+}
+
+#[test]
+fn stepping_is_traced_too() {
+    let mut cpu = Cpu::new(assemble(LOOPING).unwrap()).with_trace();
+    cpu.step().unwrap();
+    cpu.step().unwrap();
+    assert_eq!(cpu.trace().unwrap().last().unwrap().acc_after, 1);
+}
+
+#[test]
+fn exports_csv_and_json_lines() {
+    let mut cpu = Cpu::new(assemble("acc +2\njmp +2\nacc +5\nacc -1\n").unwrap()).with_trace();
+    cpu.run();
+
+    let mut csv = Vec::new();
+    trace::export(cpu.trace().unwrap(), Format::Csv, &mut csv).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "step,pc,op,arg,acc_before,acc_after\n0,0,acc,2,0,2\n1,1,jmp,2,2,2\n2,3,acc,-1,2,1\n"
+    );
+
+    let mut json = Vec::new();
+    trace::export(cpu.trace().unwrap(), Format::JsonLines, &mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    assert_eq!(json.lines().count(), 3);
+    assert_eq!(
+        json.lines().last().unwrap(),
+        r#"{"step":2,"pc":3,"op":"acc","arg":-1,"acc_before":2,"acc_after":1}"#
+    );
+}
+
+#[test]
+fn picks_the_format_from_the_extension() {
+    assert_eq!(
+        Format::from_path(Path::new("a/b.csv")).unwrap(),
+        Format::Csv
+    );
+    assert_eq!(
+        Format::from_path(Path::new("trace.jsonl")).unwrap(),
+        Format::JsonLines
+    );
+    assert!(Format::from_path(Path::new("trace.txt")).is_err());
+    assert!(Format::from_path(Path::new("trace")).is_err());
+}